@@ -1,4 +1,6 @@
-use realgas::compounds;
+use std::{hint::black_box, time::Instant};
+
+use realgas::{compounds, eos::{self, EquationOfState}, Gas, State};
 
 mod z;
 
@@ -9,6 +11,7 @@ const EXP_Z_AIR_CSV: &str = include_str!("exp/z_air.csv");
 
 fn main() {
     bench_z();
+    bench_z_speed();
 }
 
 fn bench_z() {
@@ -24,3 +27,27 @@ fn bench_z() {
     let air = compounds::dry_air().into();
     z::do_gas(EXP_Z_AIR_CSV, "air", &air, &[100.0, 300.0, 1000.0]);
 }
+
+/// Time the Z evaluations of each equation of state, on a grid of pressures and temperatures
+fn bench_z_speed() {
+    fn time<E: EquationOfState>(gas: &Gas) {
+        let start = Instant::now();
+        let mut count = 0;
+        for ip in 1..=200 {
+            for it in 0..200 {
+                let (p, t) = (ip as f64 * 1e5, 200.0 + it as f64 * 2.0);
+                black_box(gas.z::<E>(black_box(p), black_box(t)));
+                count += 1;
+            }
+        }
+        let ns = start.elapsed().as_nanos() as f64 / count as f64;
+        println!("{:<24} {:>8.1} ns/Z", E::NAME, ns);
+    }
+
+    let n2: Gas = compounds::N2.into();
+    time::<eos::VanDerWaals>(&n2);
+    time::<eos::RedlichKwong>(&n2);
+    time::<eos::SoaveRedlichKwong>(&n2);
+    time::<eos::PengRobinson>(&n2);
+    time::<eos::PatelTejaValderrama>(&n2);
+}
@@ -1,131 +1,30 @@
-use plotters::{element::DashedPathElement, style::{Color, ShapeStyle, BLUE, CYAN, GREEN, MAGENTA, RED, YELLOW}};
-use realgas::{eos::{self, EquationOfState}, Gas, State};
+use plotters::{element::DashedPathElement, style::{Color, RGBColor, ShapeStyle, BLACK, BLUE, CYAN, GREEN, MAGENTA, RED, YELLOW}};
+use realgas::{eos::{self, EquationOfState}, Gas, PtGrid};
 
-#[derive(Debug, Clone, PartialEq)]
-pub struct Row {
-    pub t: f64,
-    pub z: Vec<f64>,
-}
-
-#[derive(Debug, Clone, PartialEq)]
-pub struct Data {
-    pub pcols: Vec<f64>,
-    pub zrows: Vec<Row>,
-}
-
-impl Data {
-    pub fn new() -> Self {
-        Data {
-            pcols: Vec::new(),
-            zrows: Vec::new(),
-        }
-    }
-
-    pub fn row(&self, t: f64) -> Option<&Row> {
-        self.zrows.iter().find(|row| (row.t - t).abs() < f64::EPSILON)
-    }
-
-    pub fn pressures(&self) -> &[f64] {
-        &self.pcols
-    }
-
-    pub fn temperatures(&self) -> Vec<f64> {
-        self.zrows.iter().map(|row| row.t).collect()
-    }
-
-    pub fn gen_eos<E: EquationOfState>(gas: &Gas, pressures: &[f64], temperatures: &[f64]) -> Data {
-        let mut data = Data {
-            pcols: pressures.to_vec(),
-            zrows: Vec::new(),
-        };
-
-        for &t in temperatures {
-            let mut z_row = Row { t, z: Vec::new() };
-            for &p in pressures {
-                let z = gas.z::<E>(p, t);
-                z_row.z.push(z);
-            }
-            data.zrows.push(z_row);
-        }
-
-        data
-    }
-
-    pub fn from_csv(csv_data: &str) -> Self {
-        let mut rdr = csv::ReaderBuilder::new()
-            .has_headers(true)
-            .from_reader(csv_data.as_bytes());
-
-        let mut data = Data::new();
-
-        let head = rdr.headers().expect("Failed to read headers");
-        for header in head.iter().skip(1) {
-            let p = header.parse::<f64>().unwrap() * 1e5;
-            data.pcols.push(p);
-        }
-
-        for result in rdr.records() {
-            match result {
-                Ok(record) => {
-                    let t = record.get(0).unwrap().parse().unwrap();
-                    let mut z = Vec::new();
-                    for field in record.iter().skip(1) {
-                        let value = if field.is_empty() {
-                            f64::NAN
-                        } else {
-                            field.trim().parse().expect("Failed to parse field")
-                        };
-                        z.push(value);
-                    }
-                    data.zrows.push(Row { t, z });
-                }
-                Err(e) => eprintln!("Error reading record: {}", e),
-            }
-        }
-
-        data
-    }
-
-    pub fn _to_csv(&self) -> String {
-        let mut wtr = csv::Writer::from_writer(vec![]);
-        wtr.write_record(std::iter::once("T".to_string()).chain(self.pcols.iter().map(|p| (p * 1e-5).to_string())))
-            .expect("Failed to write header");
-
-        for row in &self.zrows {
-            let record: Vec<String> = std::iter::once(row.t.to_string())
-                .chain(row.z.iter().map(|z| z.to_string()))
-                .collect();
-            wtr.write_record(record).expect("Failed to write record");
-        }
-
-        String::from_utf8(wtr.into_inner().expect("Failed to get inner writer")).expect("Failed to convert to string")
-    }
-
-    pub fn _write_csv(&self, filename: &str) {
-        let csv_data = self._to_csv();
-        std::fs::write(filename, csv_data)
-            .expect("Failed to write CSV file");
-    }
+fn gen_eos<E: EquationOfState>(gas: &Gas, exp: &PtGrid) -> PtGrid {
+    let mut grid = PtGrid::new(exp.pressures(), exp.temperatures());
+    grid.fill_z::<E, _>(gas);
+    grid
 }
 
 struct Series<'a> {
     name: &'a str,
-    data: &'a Data,
+    data: &'a PtGrid,
     style: ShapeStyle,
     dashed: bool,
 }
 
 pub fn do_gas(exp_csv: &str, gas_name: &str, gas: &Gas, plot_temps: &[f64]) {
     
-    let exp = Data::from_csv(exp_csv);
-    let pressures = exp.pressures();
-    let temperatures = exp.temperatures();
+    let exp = PtGrid::from_csv_reader(exp_csv.as_bytes()).expect("Failed to read CSV data");
 
-    let vdw = Data::gen_eos::<eos::VanDerWaals>(gas, pressures, &temperatures);
-    let rk = Data::gen_eos::<eos::RedlichKwong>(gas, pressures, &temperatures);
-    let srk = Data::gen_eos::<eos::SoaveRedlichKwong>(gas, pressures, &temperatures);
-    let pr = Data::gen_eos::<eos::PengRobinson>(gas, pressures, &temperatures);
-    let ptv = Data::gen_eos::<eos::PatelTejaValderrama>(gas, pressures, &temperatures);
+    let vdw = gen_eos::<eos::VanDerWaals>(gas, &exp);
+    let clausius = gen_eos::<eos::Clausius>(gas, &exp);
+    let berthelot = gen_eos::<eos::Berthelot>(gas, &exp);
+    let rk = gen_eos::<eos::RedlichKwong>(gas, &exp);
+    let srk = gen_eos::<eos::SoaveRedlichKwong>(gas, &exp);
+    let pr = gen_eos::<eos::PengRobinson>(gas, &exp);
+    let ptv = gen_eos::<eos::PatelTejaValderrama>(gas, &exp);
 
     let series = &[
         Series {
@@ -135,31 +34,43 @@ pub fn do_gas(exp_csv: &str, gas_name: &str, gas: &Gas, plot_temps: &[f64]) {
             dashed: true,
         },
         Series {
-            name: "Van der Waals",
+            name: eos::VanDerWaals::NAME,
             data: &vdw,
             style: RED.into(),
             dashed: false,
         },
         Series {
-            name: "Redlich-Kwong",
+            name: eos::Clausius::NAME,
+            data: &clausius,
+            style: BLACK.into(),
+            dashed: false,
+        },
+        Series {
+            name: eos::Berthelot::NAME,
+            data: &berthelot,
+            style: RGBColor(255, 128, 0).into(),
+            dashed: false,
+        },
+        Series {
+            name: eos::RedlichKwong::NAME,
             data: &rk,
             style: YELLOW.into(),
             dashed: false,
         },
         Series {
-            name: "Soave-Redlich-Kwong",
+            name: eos::SoaveRedlichKwong::NAME,
             data: &srk,
             style: GREEN.into(),
             dashed: false,
         },
         Series {
-            name: "Peng-Robinson",
+            name: eos::PengRobinson::NAME,
             data: &pr,
             style: CYAN.into(),
             dashed: false,
         },
         Series {
-            name: "Patel-Teja-Valderrama",
+            name: eos::PatelTejaValderrama::NAME,
             data: &ptv,
             style: MAGENTA.into(),
             dashed: false,
@@ -187,8 +98,8 @@ fn plot_bench(gas_name: &str, series: &[Series], temperature: f64, path: &str) {
         .map(|Series { data, .. } | {
             let row = data.row(temperature).expect("No data for this temperature");
             (
-                row.z.iter().cloned().fold(f64::INFINITY, f64::min),
-                row.z.iter().cloned().fold(f64::NEG_INFINITY, f64::max),
+                row.iter().cloned().fold(f64::INFINITY, f64::min),
+                row.iter().cloned().fold(f64::NEG_INFINITY, f64::max),
             )
         })
         .fold(
@@ -233,7 +144,7 @@ fn plot_bench(gas_name: &str, series: &[Series], temperature: f64, path: &str) {
         if s.dashed {
             chart
                 .draw_series(DashedLineSeries::new(
-                    pressures.iter().zip(row.z.iter()).map(|(p, z)| (p * 1e-5, *z)),
+                    pressures.iter().zip(row.iter()).map(|(p, z)| (p * 1e-5, *z)),
                     5, 5,
                     s.style,
                 ))
@@ -243,8 +154,8 @@ fn plot_bench(gas_name: &str, series: &[Series], temperature: f64, path: &str) {
         } else {
             chart
                 .draw_series(LineSeries::new(
-                    pressures.iter().zip(row.z.iter()).map(|(p, z)| (p * 1e-5, *z)),
-                    s.style.clone(),
+                    pressures.iter().zip(row.iter()).map(|(p, z)| (p * 1e-5, *z)),
+                    s.style,
                 ))
                 .unwrap()
                 .label(s.name)
@@ -256,8 +167,8 @@ fn plot_bench(gas_name: &str, series: &[Series], temperature: f64, path: &str) {
         .configure_series_labels()
         .position(SeriesLabelPosition::LowerRight)
         .label_font(("sans-serif", 20))
-        .background_style(&WHITE)
-        .border_style(&BLACK)
+        .background_style(WHITE)
+        .border_style(BLACK)
         .draw()
         .unwrap();
 
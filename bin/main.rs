@@ -1,7 +1,19 @@
-use std::{fmt, num::ParseFloatError, process::ExitCode, str::FromStr};
+use std::{
+    fmt,
+    fs::File,
+    io::{self, BufRead, BufReader, Write},
+    num::ParseFloatError,
+    path::PathBuf,
+    process::ExitCode,
+    str::FromStr,
+};
 
 use clap::{Parser, Subcommand};
-use realgas::{Gas, StateEos, eos::Eos};
+use realgas::{
+    Gas, PhaseRoots, StateEos, compounds,
+    eos::{self, Eos},
+    units::Pressure,
+};
 
 /// Utility that performs real gas physics calculations.
 #[derive(Parser, Debug)]
@@ -19,19 +31,41 @@ enum Command {
         #[arg(short = 'g', long)]
         gas: String,
 
-        /// Equation of state used for computation
-        #[arg(short='e', long, default_value_t=String::from("PR"))]
-        eos: String,
+        /// Equation of state used for computation.
+        /// Defaults to an equation recommended for the gas.
+        #[arg(short = 'e', long)]
+        eos: Option<String>,
 
-        /// Specify the pressure or range of abs. pressure in bar
+        /// Specify the pressure or range of pressure in bar.
+        /// Pressures are absolute, unless --gauge is given.
         #[arg(short = 'p', long)]
         pressure: String,
 
+        /// Pressures are gauge pressures, relative to one standard atmosphere (1.01325 bar)
+        #[arg(long)]
+        gauge: bool,
+
         /// Specify the pressure or range of temperature in °C
         #[arg(short = 't', long)]
         #[clap(allow_hyphen_values = true)]
         temperature: String,
+
+        /// Suffix with '*' the Z values of states in the two-phase region,
+        /// where Z may belong to a metastable branch
+        #[arg(long)]
+        mark_two_phase: bool,
+    },
+    /// Compute Z for each case of a CSV file and print the cases with their Z to stdout
+    Batch {
+        /// CSV file with a header line, then one case per line: gas,eos,pressure,temperature.
+        /// Pressures are absolute in bar and temperatures in °C.
+        /// An empty eos selects the equation recommended for the gas.
+        file: PathBuf,
     },
+    /// List the symbols of the available gases
+    ListGases,
+    /// List the codes of the available equations of state
+    ListEos,
 }
 
 fn main() -> ExitCode {
@@ -52,18 +86,22 @@ fn run(cli: &Cli) -> anyhow::Result<()> {
             gas,
             eos,
             pressure,
+            gauge,
             temperature,
+            mark_two_phase,
         } => {
             let gas: Gas = gas.parse()?;
-            let eos: Eos = eos.parse()?;
+            let eos: Eos = match eos {
+                Some(eos) => eos.parse()?,
+                None => eos::recommended_eos(&gas),
+            };
             let pressure: Var = pressure.parse()?;
             let temperature: Var = temperature.parse()?;
             match (pressure, temperature) {
                 (Var::Scalar(p), Var::Scalar(t)) => {
-                    let p = p * 1e5;
+                    let p = absolute_pressure(p, *gauge);
                     let t = t + 273.15;
-                    let z = gas.z_eos(eos, p, t);
-                    println!("{z}");
+                    println!("{}", format_z(&gas, eos, p, t, *mark_two_phase));
                 }
                 (p, t) => {
                     let p = p.to_vec();
@@ -80,24 +118,111 @@ fn run(cli: &Cli) -> anyhow::Result<()> {
                     for p in p.iter() {
                         print!(",{p}");
                     }
-                    print!("\n");
+                    println!();
                     for t in t.iter().copied() {
                         print!("{t}");
                         let t = t + 273.15;
                         for p in p.iter() {
-                            let p = p * 1e5;
-                            let z = gas.z_eos(eos, p, t);
-                            print!(",{z}");
+                            let p = absolute_pressure(*p, *gauge);
+                            print!(",{}", format_z(&gas, eos, p, t, *mark_two_phase));
                         }
-                        print!("\n");
+                        println!();
                     }
                 }
             }
         }
+        Command::Batch { file } => {
+            let input = BufReader::new(File::open(file)?);
+            batch(input, io::stdout().lock())?;
+        }
+        Command::ListGases => list_gases(io::stdout().lock())?,
+        Command::ListEos => list_eos(io::stdout().lock())?,
+    }
+    Ok(())
+}
+
+/// Format the compression factor, marked with '*' in the two-phase region if `mark_two_phase`
+fn format_z(gas: &Gas, eos: Eos, p: f64, t: f64, mark_two_phase: bool) -> String {
+    let z = gas.z_eos(eos, p, t);
+    if mark_two_phase && matches!(gas.phase_roots_eos(eos, p, t), PhaseRoots::Triple { .. }) {
+        format!("{z}*")
+    } else {
+        z.to_string()
+    }
+}
+
+/// Write the cases of `input` to `out` with an appended z column.
+///
+/// A case that fails reports its error in the z column, without aborting the batch.
+fn batch<R: BufRead, W: Write>(input: R, mut out: W) -> io::Result<()> {
+    let mut lines = input.lines();
+    let Some(header) = lines.next() else {
+        return Ok(());
+    };
+    writeln!(out, "{},z", header?.trim_end())?;
+    for line in lines {
+        let line = line?;
+        let line = line.trim_end();
+        if line.is_empty() {
+            continue;
+        }
+        match batch_case(line) {
+            Ok(z) => writeln!(out, "{line},{z}")?,
+            Err(err) => writeln!(out, "{line},\"error: {}\"", err.to_string().replace('"', "\"\""))?,
+        }
+    }
+    Ok(())
+}
+
+/// Compute Z for a batch case line: gas,eos,pressure,temperature
+fn batch_case(line: &str) -> anyhow::Result<f64> {
+    let fields: Vec<&str> = line.split(',').map(str::trim).collect();
+    let [gas, eos, p, t] = fields[..] else {
+        anyhow::bail!("Expected 4 fields, found {}", fields.len());
+    };
+    let gas: Gas = gas.parse()?;
+    let eos: Eos = if eos.is_empty() {
+        eos::recommended_eos(&gas)
+    } else {
+        eos.parse()?
+    };
+    let p = absolute_pressure(p.parse()?, false);
+    let t = t.parse::<f64>()? + 273.15;
+    if t < 0.0 {
+        anyhow::bail!("Temperature below zero K !");
+    }
+    let (z, _) = gas.try_z_eos(eos, p, t)?;
+    Ok(z)
+}
+
+fn list_gases<W: Write>(mut out: W) -> io::Result<()> {
+    let symbols = compounds::symbols()
+        .iter()
+        .chain(["dry_air", "natural_gas"].iter());
+    for symbol in symbols {
+        let name = compounds::name(symbol).unwrap_or_default();
+        writeln!(out, "{symbol:<12}{name}")?;
     }
     Ok(())
 }
 
+fn list_eos<W: Write>(mut out: W) -> io::Result<()> {
+    for eos in Eos::all() {
+        writeln!(out, "{:<12}{}", eos.code().to_uppercase(), eos.name())?;
+    }
+    Ok(())
+}
+
+/// Convert a pressure in bar given on the command line to an absolute pressure in Pa
+fn absolute_pressure(p: f64, gauge: bool) -> f64 {
+    let p = Pressure::bar(p).to_pascal();
+    if gauge {
+        p + Pressure::atm(1.0).to_pascal()
+    } else {
+        p
+    }
+}
+
 enum Var {
     Scalar(f64),
     Range {
@@ -109,9 +234,9 @@ enum Var {
 
 impl Var {
     fn to_vec(&self) -> Vec<f64> {
-        match self {
-            &Var::Scalar(v) => vec![v],
-            &Var::Range { start, end, step } => {
+        match *self {
+            Var::Scalar(v) => vec![v],
+            Var::Range { start, end, step } => {
                 let step = step.unwrap_or(1.0);
                 let cap = ((end - start) / step) as usize;
                 let mut res = Vec::with_capacity(cap);
@@ -212,3 +337,62 @@ impl FromStr for Var {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{absolute_pressure, batch, format_z, list_eos, list_gases};
+    use realgas::{StateEos, compounds, eos::Eos};
+
+    #[test]
+    fn gauge_pressure() {
+        assert_eq!(absolute_pressure(10.0, false), 10e5);
+        assert_eq!(absolute_pressure(10.0, true), 10e5 + 101325.0);
+
+        let air = compounds::dry_air();
+        let t = 288.15;
+        let z_gauge = air.z_eos(Eos::PengRobinson, absolute_pressure(200.0, true), t);
+        let z_abs = air.z_eos(Eos::PengRobinson, 200e5 + 101325.0, t);
+        assert_eq!(z_gauge, z_abs);
+        assert_ne!(z_gauge, air.z_eos(Eos::PengRobinson, absolute_pressure(200.0, false), t));
+    }
+
+    #[test]
+    fn lists() {
+        let mut out = Vec::new();
+        list_gases(&mut out).unwrap();
+        let out = String::from_utf8(out).unwrap();
+        assert!(out.lines().any(|l| l.starts_with("N2 ") && l.ends_with("Nitrogen")));
+        assert!(out.contains("dry_air"));
+
+        let mut out = Vec::new();
+        list_eos(&mut out).unwrap();
+        let out = String::from_utf8(out).unwrap();
+        assert!(out.lines().any(|l| l.starts_with("PR ") && l.ends_with("Peng-Robinson")));
+    }
+
+    #[test]
+    fn two_phase_marker() {
+        let co2 = compounds::CO2.into();
+        let two_phase = format_z(&co2, Eos::PengRobinson, 40e5, 280.0, true);
+        assert!(two_phase.ends_with('*'));
+        assert!(!format_z(&co2, Eos::PengRobinson, 40e5, 280.0, false).ends_with('*'));
+        assert!(!format_z(&co2, Eos::PengRobinson, 1e5, 280.0, true).ends_with('*'));
+        assert!(!format_z(&co2, Eos::PengRobinson, 100e5, 350.0, true).ends_with('*'));
+    }
+
+    #[test]
+    fn batch_errors() {
+        let input = "gas,eos,pressure,temperature\nN2,,200,15\nN2,foo,200,15\nN2,pr,200\nN2,pr,200,-300\n";
+        let mut out = Vec::new();
+        batch(input.as_bytes(), &mut out).unwrap();
+        let out = String::from_utf8(out).unwrap();
+        let lines: Vec<&str> = out.lines().collect();
+        assert_eq!(lines.len(), 5);
+        assert_eq!(lines[0], "gas,eos,pressure,temperature,z");
+        let z = compounds::N2.z_eos(Eos::PengRobinson, 200e5, 288.15);
+        assert_eq!(lines[1], format!("N2,,200,15,{z}"));
+        assert!(lines[2].starts_with("N2,foo,200,15,\"error: "));
+        assert!(lines[3].starts_with("N2,pr,200,\"error: Expected 4 fields"));
+        assert!(lines[4].ends_with("below zero K !\""));
+    }
+}
@@ -1,6 +1,15 @@
 /// Physical constants of gas molecules
 /// source: http://www.kaylaiacovino.com/Petrology_Tools/Critical_Constants_and_Acentric_Factors.htm
-use crate::{Gas, Mixture, Molecule, Pvt};
+/// Validity ranges are those of the NIST reference equations of state of each fluid.
+/// Ideal gas heat capacities are from Poling, Prausnitz and O'Connell (2001).
+use alloc::{collections::BTreeMap, string::String};
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+
+use crate::{
+    Gas, GasParseError, IdealCp, Mixture, Molecule, Pvt, QuantumCorrection, ValidityRange,
+    eos::AlphaFunction,
+};
 
 pub fn lookup<S>(name: S) -> Option<Gas>
 where
@@ -8,42 +17,193 @@ where
 {
     match name.as_ref() {
         "dry_air" => Some(dry_air().into()),
-        "Ar" => Some(AR.into()),
-        "Br2" => Some(BR2.into()),
-        "Cl2" => Some(CL2.into()),
-        "F2" => Some(F2.into()),
-        "He" => Some(HE.into()),
-        "H2" => Some(H2.into()),
-        "I2" => Some(I2.into()),
-        "Kr" => Some(KR.into()),
-        "Ne" => Some(NE.into()),
-        "N2" => Some(N2.into()),
-        "O2" => Some(O2.into()),
-        "Xe" => Some(XE.into()),
-        "C2H2" => Some(C2H2.into()),
-        "C6H6" => Some(C6H6.into()),
-        "C4H10" => Some(C4H10.into()),
-        "C4H8" => Some(C4H8.into()),
-        "C6H12" => Some(C6H12.into()),
-        "C3H6" => Some(C3H6.into()),
-        "C2H6" => Some(C2H6.into()),
-        "C2H4" => Some(C2H4.into()),
-        "NH3" => Some(NH3.into()),
-        "CO2" => Some(CO2.into()),
-        "CO" => Some(CO.into()),
-        "NO" => Some(NO.into()),
-        "SO2" => Some(SO2.into()),
-        "SO3" => Some(SO3.into()),
-        "H2O" => Some(H2O.into()),
-        "CH3COOH" => Some(CH3COOH.into()),
-        "C3H6O" => Some(C3H6O.into()),
-        "C2H5OH" => Some(C2H5OH.into()),
-        "CH3OH" => Some(CH3OH.into()),
-        "CH3CL" => Some(CH3CL.into()),
-        _ => None,
+        "natural_gas" => Some(natural_gas().into()),
+        name => MOLECULES
+            .iter()
+            .find(|(symbol, _, _)| *symbol == name)
+            .map(|(_, _, m)| (*m).into()),
+    }
+}
+
+/// A set of compounds, where user overrides are consulted before the built-in constants.
+///
+/// This allows to correct or update the data of specific compounds.
+/// Note that the built-in mixtures (`dry_air`, `natural_gas`) are not affected by overrides.
+#[derive(Debug, Clone, Default)]
+pub struct Compounds {
+    overrides: BTreeMap<String, Molecule>,
+}
+
+impl Compounds {
+    /// The built-in compounds, without override
+    pub fn new() -> Self {
+        Compounds::default()
+    }
+
+    /// These compounds, with `symbol` resolving to `molecule`
+    pub fn with_override<S: Into<String>>(mut self, symbol: S, molecule: Molecule) -> Self {
+        self.overrides.insert(symbol.into(), molecule);
+        self
+    }
+
+    /// Lookup a compound by name, in the overrides first, then in the built-in compounds
+    pub fn lookup<S: AsRef<str>>(&self, name: S) -> Option<Gas> {
+        match self.overrides.get(name.as_ref()) {
+            Some(m) => Some((*m).into()),
+            None => lookup(name),
+        }
+    }
+
+    /// Parse a gas like [`Gas::from_str`](core::str::FromStr), with the overridden compounds
+    pub fn parse_gas(&self, s: &str) -> Result<Gas, GasParseError> {
+        crate::gas::parse_gas(s, self)
+    }
+}
+
+/// The symbols and molecules of the built-in compounds.
+///
+/// The built-in mixtures are not included, see [`dry_air`] and [`natural_gas`].
+pub fn all() -> impl Iterator<Item = (&'static str, Molecule)> {
+    MOLECULES.iter().map(|(symbol, _, m)| (*symbol, *m))
+}
+
+/// The name of the built-in compound or mixture of `symbol`, e.g. "Nitrogen" for "N2"
+pub fn name(symbol: &str) -> Option<&'static str> {
+    match symbol {
+        "dry_air" => Some("Dry air"),
+        "natural_gas" => Some("Natural gas"),
+        symbol => MOLECULES
+            .iter()
+            .find(|(s, _, _)| *s == symbol)
+            .map(|(_, name, _)| *name),
+    }
+}
+
+/// The symbols of the built-in compounds, as recognized by [`lookup`]
+pub fn symbols() -> &'static [&'static str] {
+    &SYMBOLS
+}
+
+/// The provenance of the critical constants of a built-in compound.
+///
+/// The source does not give uncertainties: `tc_unc` and `pc_unc` are placeholder estimates,
+/// half of the last digit the table gives (0.1 K and 0.1 bar, or ten times less for helium).
+/// They are not the measurement uncertainties of the critical constants.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CompoundMeta {
+    /// The reference the critical constants are taken from
+    pub source: &'static str,
+    /// The estimated rounding of the critical temperature, in K (placeholder, see above)
+    pub tc_unc: f64,
+    /// The estimated rounding of the critical pressure, in Pa (placeholder, see above)
+    pub pc_unc: f64,
+}
+
+const SOURCE: &str = "http://www.kaylaiacovino.com/Petrology_Tools/Critical_Constants_and_Acentric_Factors.htm";
+
+/// The [`CompoundMeta`] of `molecule`, or `None` if it is not a built-in compound
+pub(crate) fn meta(molecule: &Molecule) -> Option<CompoundMeta> {
+    let (tc_unc, pc_unc) = match symbol(molecule)? {
+        "He" => (0.005, 0.005e5),
+        _ => (0.05, 0.05e5),
+    };
+    Some(CompoundMeta {
+        source: SOURCE,
+        tc_unc,
+        pc_unc,
+    })
+}
+
+/// The symbol of a built-in compound
+pub(crate) fn symbol(molecule: &Molecule) -> Option<&'static str> {
+    MOLECULES
+        .iter()
+        .find(|(_, _, m)| m == molecule)
+        .map(|(symbol, _, _)| *symbol)
+}
+
+/// Suggest the symbol of a known compound close to `name`, if any
+pub(crate) fn suggest(name: &str) -> Option<&'static str> {
+    ["dry_air", "natural_gas"]
+        .into_iter()
+        .chain(MOLECULES.iter().map(|(symbol, _, _)| *symbol))
+        .map(|symbol| (edit_distance(name, symbol), symbol))
+        .filter(|(dist, _)| *dist <= 2 && *dist < name.len())
+        .min_by_key(|(dist, _)| *dist)
+        .map(|(_, symbol)| symbol)
+}
+
+/// The Levenshtein distance between two strings
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut row: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut diag = row[0];
+        row[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let sub = diag + usize::from(ca != *cb);
+            diag = row[j + 1];
+            row[j + 1] = sub.min(row[j] + 1).min(row[j + 1] + 1);
+        }
     }
+    row[b.len()]
 }
 
+/// The symbols, names and molecules of the built-in compounds
+const MOLECULES: &[(&str, &str, Molecule)] = &[
+    ("Ar", "Argon", AR),
+    ("Br2", "Bromine", BR2),
+    ("Cl2", "Chlorine", CL2),
+    ("F2", "Fluorine", F2),
+    ("He", "Helium", HE),
+    ("H2", "Hydrogen", H2),
+    ("I2", "Iodine", I2),
+    ("Kr", "Krypton", KR),
+    ("Ne", "Neon", NE),
+    ("N2", "Nitrogen", N2),
+    ("O2", "Oxygen", O2),
+    ("Xe", "Xenon", XE),
+    ("Rn", "Radon", RN),
+    ("C2H2", "Acetylene", C2H2),
+    ("C6H6", "Benzene", C6H6),
+    ("C4H10", "Butane", C4H10),
+    ("iC4H10", "Isobutane", IC4H10),
+    ("C4H8", "Cyclobutane", C4H8),
+    ("C6H12", "Cyclohexane", C6H12),
+    ("C3H6", "Cyclopropane", C3H6),
+    ("C2H6", "Ethane", C2H6),
+    ("C2H4", "Ethylene", C2H4),
+    ("CH4", "Methane", CH4),
+    ("C3H8", "Propane", C3H8),
+    ("C5H12", "Pentane", C5H12),
+    ("NH3", "Ammonia", NH3),
+    ("CO2", "Carbon dioxide", CO2),
+    ("CO", "Carbon monoxide", CO),
+    ("NO", "Nitric oxide", NO),
+    ("N2O", "Nitrous oxide", N2O),
+    ("NO2", "Nitrogen dioxide", NO2),
+    ("SO2", "Sulfur dioxide", SO2),
+    ("SO3", "Sulfur trioxide", SO3),
+    ("H2S", "Hydrogen sulfide", H2S),
+    ("H2O", "Water", H2O),
+    ("CH3COOH", "Acetic acid", CH3COOH),
+    ("C3H6O", "Acetone", C3H6O),
+    ("C2H5OH", "Ethanol", C2H5OH),
+    ("CH3OH", "Methanol", CH3OH),
+    ("CH3CL", "Methyl chloride", CH3CL),
+];
+
+/// The symbols of the built-in compounds, in the order of `MOLECULES`
+const SYMBOLS: [&str; MOLECULES.len()] = {
+    let mut symbols = [""; MOLECULES.len()];
+    let mut i = 0;
+    while i < MOLECULES.len() {
+        symbols[i] = MOLECULES[i].0;
+        i += 1;
+    }
+    symbols
+};
+
 /// Air mixture
 pub fn dry_air() -> Mixture {
     use crate::gas::Comp;
@@ -56,6 +216,42 @@ pub fn dry_air() -> Mixture {
     .unwrap()
 }
 
+/// Natural gas mixture, with a representative pipeline composition
+pub fn natural_gas() -> Mixture {
+    use crate::gas::Comp;
+    Mixture::new(&[
+        Comp::Factor(0.05, C2H6.into()),
+        Comp::Factor(0.015, C3H8.into()),
+        Comp::Factor(0.003, C4H10.into()),
+        Comp::Factor(0.002, IC4H10.into()),
+        Comp::Factor(0.01, N2.into()),
+        Comp::Factor(0.01, CO2.into()),
+        Comp::Remainder(CH4.into()),
+    ])
+    .unwrap()
+}
+
+/// Quantum correction of hydrogen (Chueh and Prausnitz, 1967)
+pub const H2_QUANTUM_CORRECTION: QuantumCorrection = QuantumCorrection {
+    tc0: 43.6,
+    pc0: 20.5 * 1e5,
+    vc0: 51.5 * 1e-6,
+};
+
+/// Quantum correction of helium-4 (Chueh and Prausnitz, 1967)
+pub const HE_QUANTUM_CORRECTION: QuantumCorrection = QuantumCorrection {
+    tc0: 10.47,
+    pc0: 6.76 * 1e5,
+    vc0: 37.5 * 1e-6,
+};
+
+/// Quantum correction of neon (Chueh and Prausnitz, 1967)
+pub const NE_QUANTUM_CORRECTION: QuantumCorrection = QuantumCorrection {
+    tc0: 45.5,
+    pc0: 27.3 * 1e5,
+    vc0: 40.3 * 1e-6,
+};
+
 /// Argon
 pub const AR: Molecule = Molecule {
     critical_state: Pvt {
@@ -63,8 +259,17 @@ pub const AR: Molecule = Molecule {
         v: 74.9 * 1e-6,
         t: 150.8,
     },
-    w: 0.001,
+    w: Some(0.001),
     m: 0.039948,
+    dipole: 0.0,
+    quantum: None,
+    alpha: AlphaFunction::Standard,
+    validity: Some(ValidityRange {
+        t_min: 83.8,
+        t_max: 2000.0,
+        p_max: 1000.0 * 1e6,
+    }),
+    cp: Some(IdealCp::MONATOMIC),
 };
 
 /// Bromine
@@ -74,8 +279,13 @@ pub const BR2: Molecule = Molecule {
         v: 127.2 * 1e-6,
         t: 588.0,
     },
-    w: 0.108,
+    w: Some(0.108),
     m: 0.159808,
+    dipole: 0.0,
+    quantum: None,
+    alpha: AlphaFunction::Standard,
+    validity: None,
+    cp: None,
 };
 
 /// Chlore
@@ -85,8 +295,13 @@ pub const CL2: Molecule = Molecule {
         v: 123.8 * 1e-6,
         t: 416.9,
     },
-    w: 0.09,
+    w: Some(0.09),
     m: 0.070906,
+    dipole: 0.0,
+    quantum: None,
+    alpha: AlphaFunction::Standard,
+    validity: None,
+    cp: None,
 };
 
 /// Fluor
@@ -96,8 +311,13 @@ pub const F2: Molecule = Molecule {
         v: 66.3 * 1e-6,
         t: 144.3,
     },
-    w: 0.054,
+    w: Some(0.054),
     m: 0.0379968,
+    dipole: 0.0,
+    quantum: None,
+    alpha: AlphaFunction::Standard,
+    validity: None,
+    cp: None,
 };
 
 /// Helium
@@ -107,8 +327,17 @@ pub const HE: Molecule = Molecule {
         v: 57.4 * 1e-6,
         t: 5.19,
     },
-    w: -0.365,
+    w: Some(-0.365),
     m: 0.004002602,
+    dipole: 0.0,
+    quantum: None,
+    alpha: AlphaFunction::Standard,
+    validity: Some(ValidityRange {
+        t_min: 2.1768,
+        t_max: 2000.0,
+        p_max: 1000.0 * 1e6,
+    }),
+    cp: Some(IdealCp::MONATOMIC),
 };
 
 /// Hydrogen
@@ -118,8 +347,19 @@ pub const H2: Molecule = Molecule {
         v: 64.3 * 1e-6,
         t: 33.0,
     },
-    w: -0.216,
+    w: Some(-0.216),
     m: 0.00201588,
+    dipole: 0.0,
+    quantum: None,
+    alpha: AlphaFunction::Standard,
+    validity: Some(ValidityRange {
+        t_min: 13.957,
+        t_max: 1000.0,
+        p_max: 2000.0 * 1e6,
+    }),
+    cp: Some(IdealCp {
+        a: [2.883, 3.681 * 1e-3, -0.772 * 1e-5, 0.692 * 1e-8, -0.213 * 1e-11],
+    }),
 };
 
 /// Iode
@@ -129,8 +369,13 @@ pub const I2: Molecule = Molecule {
         v: 155.0 * 1e-6,
         t: 819.0,
     },
-    w: 0.229,
+    w: Some(0.229),
     m: 0.25380894,
+    dipole: 0.0,
+    quantum: None,
+    alpha: AlphaFunction::Standard,
+    validity: None,
+    cp: None,
 };
 
 /// Krypton
@@ -140,8 +385,13 @@ pub const KR: Molecule = Molecule {
         v: 91.2 * 1e-6,
         t: 209.4,
     },
-    w: 0.005,
+    w: Some(0.005),
     m: 0.083798,
+    dipole: 0.0,
+    quantum: None,
+    alpha: AlphaFunction::Standard,
+    validity: None,
+    cp: Some(IdealCp::MONATOMIC),
 };
 
 /// Neon
@@ -151,8 +401,13 @@ pub const NE: Molecule = Molecule {
         v: 41.6 * 1e-6,
         t: 44.4,
     },
-    w: -0.029,
+    w: Some(-0.029),
     m: 0.0201797,
+    dipole: 0.0,
+    quantum: None,
+    alpha: AlphaFunction::Standard,
+    validity: None,
+    cp: Some(IdealCp::MONATOMIC),
 };
 
 /// Nitrogen
@@ -162,8 +417,19 @@ pub const N2: Molecule = Molecule {
         v: 89.8 * 1e-6,
         t: 126.2,
     },
-    w: 0.039,
+    w: Some(0.039),
     m: 0.0280134,
+    dipole: 0.0,
+    quantum: None,
+    alpha: AlphaFunction::Standard,
+    validity: Some(ValidityRange {
+        t_min: 63.15,
+        t_max: 2000.0,
+        p_max: 2200.0 * 1e6,
+    }),
+    cp: Some(IdealCp {
+        a: [3.539, -0.261 * 1e-3, 0.007 * 1e-5, 0.157 * 1e-8, -0.099 * 1e-11],
+    }),
 };
 
 /// Oxygen
@@ -173,8 +439,19 @@ pub const O2: Molecule = Molecule {
         v: 73.4 * 1e-6,
         t: 154.6,
     },
-    w: 0.025,
+    w: Some(0.025),
     m: 0.0319988,
+    dipole: 0.0,
+    quantum: None,
+    alpha: AlphaFunction::Standard,
+    validity: Some(ValidityRange {
+        t_min: 54.36,
+        t_max: 2000.0,
+        p_max: 82.0 * 1e6,
+    }),
+    cp: Some(IdealCp {
+        a: [3.630, -1.794 * 1e-3, 0.658 * 1e-5, -0.601 * 1e-8, 0.179 * 1e-11],
+    }),
 };
 
 /// Xenon
@@ -184,8 +461,29 @@ pub const XE: Molecule = Molecule {
         v: 66.3 * 1e-6,
         t: 289.7,
     },
-    w: 0.008,
+    w: Some(0.008),
     m: 0.131293,
+    dipole: 0.0,
+    quantum: None,
+    alpha: AlphaFunction::Standard,
+    validity: None,
+    cp: Some(IdealCp::MONATOMIC),
+};
+
+/// Radon
+pub const RN: Molecule = Molecule {
+    critical_state: Pvt {
+        p: 62.8 * 1e5,
+        v: 139.0 * 1e-6,
+        t: 377.0,
+    },
+    w: Some(0.0),
+    m: 0.222,
+    dipole: 0.0,
+    quantum: None,
+    alpha: AlphaFunction::Standard,
+    validity: None,
+    cp: Some(IdealCp::MONATOMIC),
 };
 
 /// Acetylene
@@ -195,8 +493,13 @@ pub const C2H2: Molecule = Molecule {
         v: 112.7 * 1e-6,
         t: 308.3,
     },
-    w: 0.19,
+    w: Some(0.19),
     m: 0.0260373,
+    dipole: 0.0,
+    quantum: None,
+    alpha: AlphaFunction::Standard,
+    validity: None,
+    cp: None,
 };
 
 /// Benzene
@@ -206,8 +509,13 @@ pub const C6H6: Molecule = Molecule {
         v: 259.0 * 1e-6,
         t: 562.1,
     },
-    w: 0.212,
+    w: Some(0.212),
     m: 0.0781118,
+    dipole: 0.0,
+    quantum: None,
+    alpha: AlphaFunction::Standard,
+    validity: None,
+    cp: None,
 };
 
 /// Butane
@@ -217,8 +525,33 @@ pub const C4H10: Molecule = Molecule {
         v: 255.0 * 1e-6,
         t: 425.2,
     },
-    w: 0.199,
+    w: Some(0.199),
+    m: 0.0581222,
+    dipole: 0.0,
+    quantum: None,
+    alpha: AlphaFunction::Standard,
+    validity: None,
+    cp: Some(IdealCp {
+        a: [5.547, 5.536 * 1e-3, 8.057 * 1e-5, -10.571 * 1e-8, 4.134 * 1e-11],
+    }),
+};
+
+/// Isobutane
+pub const IC4H10: Molecule = Molecule {
+    critical_state: Pvt {
+        p: 36.5 * 1e5,
+        v: 263.0 * 1e-6,
+        t: 408.2,
+    },
+    w: Some(0.183),
     m: 0.0581222,
+    dipole: 0.1,
+    quantum: None,
+    alpha: AlphaFunction::Standard,
+    validity: None,
+    cp: Some(IdealCp {
+        a: [3.351, 17.883 * 1e-3, 5.477 * 1e-5, -8.099 * 1e-8, 3.243 * 1e-11],
+    }),
 };
 
 /// Cyclobutane
@@ -228,8 +561,13 @@ pub const C4H8: Molecule = Molecule {
         v: 210.0 * 1e-6,
         t: 460.0,
     },
-    w: 0.181,
+    w: Some(0.181),
     m: 0.0561063,
+    dipole: 0.0,
+    quantum: None,
+    alpha: AlphaFunction::Standard,
+    validity: None,
+    cp: None,
 };
 
 /// Cyclohexane
@@ -239,8 +577,13 @@ pub const C6H12: Molecule = Molecule {
         v: 308. * 1e-6,
         t: 553.8,
     },
-    w: 0.212,
+    w: Some(0.212),
     m: 0.0841595,
+    dipole: 0.0,
+    quantum: None,
+    alpha: AlphaFunction::Standard,
+    validity: None,
+    cp: None,
 };
 
 /// Cyclopropane
@@ -250,8 +593,13 @@ pub const C3H6: Molecule = Molecule {
         v: 163.0 * 1e-6,
         t: 397.8,
     },
-    w: 0.130,
+    w: Some(0.130),
     m: 0.0420797,
+    dipole: 0.0,
+    quantum: None,
+    alpha: AlphaFunction::Standard,
+    validity: None,
+    cp: None,
 };
 
 /// Ethane
@@ -261,8 +609,15 @@ pub const C2H6: Molecule = Molecule {
         v: 148.3 * 1e-6,
         t: 305.4,
     },
-    w: 0.099,
+    w: Some(0.099),
     m: 0.030069,
+    dipole: 0.0,
+    quantum: None,
+    alpha: AlphaFunction::Standard,
+    validity: None,
+    cp: Some(IdealCp {
+        a: [4.178, -4.427 * 1e-3, 5.660 * 1e-5, -6.651 * 1e-8, 2.487 * 1e-11],
+    }),
 };
 
 /// Ethylene
@@ -272,8 +627,69 @@ pub const C2H4: Molecule = Molecule {
         v: 130.4 * 1e-6,
         t: 282.4,
     },
-    w: 0.089,
+    w: Some(0.089),
     m: 0.0280532,
+    dipole: 0.0,
+    quantum: None,
+    alpha: AlphaFunction::Standard,
+    validity: None,
+    cp: None,
+};
+
+/// Methane
+pub const CH4: Molecule = Molecule {
+    critical_state: Pvt {
+        p: 46.0 * 1e5,
+        v: 99.2 * 1e-6,
+        t: 190.4,
+    },
+    w: Some(0.011),
+    m: 0.0160425,
+    dipole: 0.0,
+    quantum: None,
+    alpha: AlphaFunction::Standard,
+    validity: Some(ValidityRange {
+        t_min: 90.69,
+        t_max: 625.0,
+        p_max: 1000.0 * 1e6,
+    }),
+    cp: Some(IdealCp {
+        a: [4.568, -8.975 * 1e-3, 3.631 * 1e-5, -3.407 * 1e-8, 1.091 * 1e-11],
+    }),
+};
+
+/// Propane
+pub const C3H8: Molecule = Molecule {
+    critical_state: Pvt {
+        p: 42.5 * 1e5,
+        v: 203.0 * 1e-6,
+        t: 369.8,
+    },
+    w: Some(0.153),
+    m: 0.0440956,
+    dipole: 0.0,
+    quantum: None,
+    alpha: AlphaFunction::Standard,
+    validity: None,
+    cp: Some(IdealCp {
+        a: [3.847, 5.131 * 1e-3, 6.011 * 1e-5, -7.893 * 1e-8, 3.079 * 1e-11],
+    }),
+};
+
+/// Pentane
+pub const C5H12: Molecule = Molecule {
+    critical_state: Pvt {
+        p: 33.7 * 1e5,
+        v: 304.0 * 1e-6,
+        t: 469.7,
+    },
+    w: Some(0.251),
+    m: 0.0721488,
+    dipole: 0.0,
+    quantum: None,
+    alpha: AlphaFunction::Standard,
+    validity: None,
+    cp: None,
 };
 
 /// Ammonia
@@ -283,8 +699,13 @@ pub const NH3: Molecule = Molecule {
         v: 72.5 * 1e-6,
         t: 405.5,
     },
-    w: 0.250,
+    w: Some(0.250),
     m: 0.01703052,
+    dipole: 1.5,
+    quantum: None,
+    alpha: AlphaFunction::Standard,
+    validity: None,
+    cp: None,
 };
 
 /// Carbon dioxide
@@ -294,8 +715,19 @@ pub const CO2: Molecule = Molecule {
         v: 93.9 * 1e-6,
         t: 304.1,
     },
-    w: 0.239,
+    w: Some(0.239),
     m: 0.0440095,
+    dipole: 0.0,
+    quantum: None,
+    alpha: AlphaFunction::Standard,
+    validity: Some(ValidityRange {
+        t_min: 216.59,
+        t_max: 2000.0,
+        p_max: 800.0 * 1e6,
+    }),
+    cp: Some(IdealCp {
+        a: [3.259, 1.356 * 1e-3, 1.502 * 1e-5, -2.374 * 1e-8, 1.056 * 1e-11],
+    }),
 };
 
 /// Carbon monoxide
@@ -305,8 +737,15 @@ pub const CO: Molecule = Molecule {
         v: 93.2 * 1e-6,
         t: 132.9,
     },
-    w: 0.066,
+    w: Some(0.066),
     m: 0.0280101,
+    dipole: 0.1,
+    quantum: None,
+    alpha: AlphaFunction::Standard,
+    validity: None,
+    cp: Some(IdealCp {
+        a: [3.912, -3.913 * 1e-3, 1.182 * 1e-5, -1.302 * 1e-8, 0.515 * 1e-11],
+    }),
 };
 
 /// Nitric oxide
@@ -316,8 +755,48 @@ pub const NO: Molecule = Molecule {
         v: 57.7 * 1e-6,
         t: 180.0,
     },
-    w: 0.588,
+    w: Some(0.588),
     m: 0.0300061,
+    dipole: 0.2,
+    quantum: None,
+    alpha: AlphaFunction::Standard,
+    validity: None,
+    cp: None,
+};
+
+/// Nitrous oxide
+pub const N2O: Molecule = Molecule {
+    critical_state: Pvt {
+        p: 72.4 * 1e5,
+        v: 97.4 * 1e-6,
+        t: 309.6,
+    },
+    w: Some(0.165),
+    m: 0.0440128,
+    dipole: 0.2,
+    quantum: None,
+    alpha: AlphaFunction::Standard,
+    validity: None,
+    cp: None,
+};
+
+/// Nitrogen dioxide
+///
+/// NO2 dimerizes into N2O4 at low temperature and high pressure.
+/// The equations of state treat it as a single species and ignore this association.
+pub const NO2: Molecule = Molecule {
+    critical_state: Pvt {
+        p: 101.3 * 1e5,
+        v: 167.8 * 1e-6,
+        t: 431.4,
+    },
+    w: Some(0.86),
+    m: 0.0460055,
+    dipole: 0.4,
+    quantum: None,
+    alpha: AlphaFunction::Standard,
+    validity: None,
+    cp: None,
 };
 
 /// Sulfur dioxide
@@ -327,8 +806,13 @@ pub const SO2: Molecule = Molecule {
         v: 122.2 * 1e-6,
         t: 430.8,
     },
-    w: 0.256,
+    w: Some(0.256),
     m: 0.064066,
+    dipole: 1.6,
+    quantum: None,
+    alpha: AlphaFunction::Standard,
+    validity: None,
+    cp: None,
 };
 
 /// Sulfur trioxide
@@ -338,8 +822,29 @@ pub const SO3: Molecule = Molecule {
         v: 127.3 * 1e-6,
         t: 491.0,
     },
-    w: 0.481,
+    w: Some(0.481),
     m: 0.080066,
+    dipole: 0.0,
+    quantum: None,
+    alpha: AlphaFunction::Standard,
+    validity: None,
+    cp: None,
+};
+
+/// Hydrogen sulfide
+pub const H2S: Molecule = Molecule {
+    critical_state: Pvt {
+        p: 89.4 * 1e5,
+        v: 98.6 * 1e-6,
+        t: 373.2,
+    },
+    w: Some(0.090),
+    m: 0.034081,
+    dipole: 0.9,
+    quantum: None,
+    alpha: AlphaFunction::Standard,
+    validity: None,
+    cp: None,
 };
 
 /// Water
@@ -349,8 +854,19 @@ pub const H2O: Molecule = Molecule {
         v: 57.1 * 1e-6,
         t: 647.3,
     },
-    w: 0.344,
+    w: Some(0.344),
     m: 0.01801528,
+    dipole: 1.8,
+    quantum: None,
+    alpha: AlphaFunction::Standard,
+    validity: Some(ValidityRange {
+        t_min: 273.16,
+        t_max: 1273.0,
+        p_max: 1000.0 * 1e6,
+    }),
+    cp: Some(IdealCp {
+        a: [4.395, -4.186 * 1e-3, 1.405 * 1e-5, -1.564 * 1e-8, 0.632 * 1e-11],
+    }),
 };
 
 /// Acetic acid
@@ -360,8 +876,13 @@ pub const CH3COOH: Molecule = Molecule {
         v: 66.3 * 1e-6,
         t: 592.7,
     },
-    w: 0.09,
+    w: Some(0.09),
     m: 0.060052,
+    dipole: 1.3,
+    quantum: None,
+    alpha: AlphaFunction::Standard,
+    validity: None,
+    cp: None,
 };
 
 /// Acetone
@@ -371,8 +892,13 @@ pub const C3H6O: Molecule = Molecule {
         v: 209.0 * 1e-6,
         t: 508.1,
     },
-    w: 0.304,
+    w: Some(0.304),
     m: 0.0580791,
+    dipole: 2.9,
+    quantum: None,
+    alpha: AlphaFunction::Standard,
+    validity: None,
+    cp: None,
 };
 
 /// Ethanol
@@ -382,8 +908,13 @@ pub const C2H5OH: Molecule = Molecule {
         v: 167.1 * 1e-6,
         t: 513.9,
     },
-    w: 0.644,
+    w: Some(0.644),
     m: 0.04606844,
+    dipole: 1.7,
+    quantum: None,
+    alpha: AlphaFunction::Standard,
+    validity: None,
+    cp: None,
 };
 
 /// Methanol
@@ -393,8 +924,13 @@ pub const CH3OH: Molecule = Molecule {
         v: 118.0 * 1e-6,
         t: 512.6,
     },
-    w: 0.556,
+    w: Some(0.556),
     m: 0.03204294,
+    dipole: 1.7,
+    quantum: None,
+    alpha: AlphaFunction::Standard,
+    validity: None,
+    cp: None,
 };
 
 /// Methyl Chloride
@@ -404,6 +940,101 @@ pub const CH3CL: Molecule = Molecule {
         v: 138.9 * 1e-6,
         t: 416.3,
     },
-    w: 0.153,
+    w: Some(0.153),
     m: 0.0504905,
+    dipole: 1.9,
+    quantum: None,
+    alpha: AlphaFunction::Standard,
+    validity: None,
+    cp: None,
 };
+
+#[cfg(test)]
+mod tests {
+    use super::{Compounds, N2, O2, all, lookup, name, symbols};
+    use crate::{Gas, Molecule, Pvt, State, eos::PengRobinson};
+    use float_eq::assert_float_eq;
+
+    #[test]
+    fn built_in_compounds_have_meta() {
+        for (_, m) in all() {
+            let meta = m.meta().unwrap();
+            assert!(!meta.source.is_empty());
+            assert!(meta.tc_unc > 0.0 && meta.pc_unc > 0.0);
+        }
+
+        let custom = Molecule::from_critical(300.0, 50e5, 100e-6, 0.1, 0.05);
+        assert_eq!(custom.meta(), None);
+    }
+
+    #[test]
+    fn overrides_are_looked_up_first() {
+        type E = PengRobinson;
+
+        let n2 = Molecule {
+            critical_state: Pvt {
+                t: 126.19,
+                ..N2.critical_state
+            },
+            ..N2
+        };
+        let compounds = Compounds::new().with_override("N2", n2);
+        assert_eq!(compounds.lookup("N2"), Some(n2.into()));
+        assert_eq!(compounds.lookup("O2"), Some(O2.into()));
+
+        let gas = compounds.parse_gas("N2").unwrap();
+        assert_ne!(gas.z::<E>(100e5, 150.0), N2.z::<E>(100e5, 150.0));
+        assert_eq!(gas.z::<E>(100e5, 150.0), n2.z::<E>(100e5, 150.0));
+
+        let mix = compounds.parse_gas("80%N2+O2").unwrap();
+        let Gas::Mixture(mix) = mix else {
+            panic!("expected a mixture");
+        };
+        assert_float_eq!(mix.mole_fraction_of(&n2).unwrap(), 0.8, ulps <= 1);
+        assert_float_eq!(mix.mole_fraction_of(&O2).unwrap(), 0.2, r2nd <= 1e-12);
+        assert_eq!(mix.mole_fraction_of(&N2), None);
+    }
+
+    #[test]
+    fn methane_at_pipeline_conditions() {
+        let ch4: Gas = "CH4".parse().unwrap();
+        assert_eq!(ch4, Gas::from(super::CH4));
+
+        // reference value is about 0.88 at 70 bar, 15°C
+        let z = ch4.z::<PengRobinson>(70e5, 288.15);
+        assert_float_eq!(z, 0.88, r2nd <= 0.05);
+
+        let ng: Gas = "natural_gas".parse().unwrap();
+        let z_ng = ng.z::<PengRobinson>(70e5, 288.15);
+        assert!(z_ng < z);
+    }
+
+    #[test]
+    fn sour_gas_and_nitrogen_oxides() {
+        for (symbol, m) in [
+            ("H2S", super::H2S),
+            ("N2O", super::N2O),
+            ("NO2", super::NO2),
+            ("Rn", super::RN),
+        ] {
+            assert_eq!(symbol.parse::<Gas>().unwrap(), Gas::from(m));
+        }
+
+        // H2S at 10 bar, 25°C is slightly denser than the ideal gas (13.75 kg/m3)
+        let rho = super::H2S.specific_mass::<PengRobinson>(10e5, 298.15);
+        assert!(rho > 13.75 && rho < 15.5, "rho = {rho}");
+    }
+
+    #[test]
+    fn all_compounds() {
+        assert_eq!(symbols().len(), 40);
+        assert_eq!(all().count(), symbols().len());
+        assert_eq!(name("N2"), Some("Nitrogen"));
+        assert_eq!(name("dry_air"), Some("Dry air"));
+        assert_eq!(name("N3"), None);
+        for (symbol, (s, m)) in symbols().iter().zip(all()) {
+            assert_eq!(*symbol, s);
+            assert_eq!(lookup(symbol), Some(m.into()));
+        }
+    }
+}
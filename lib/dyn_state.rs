@@ -0,0 +1,93 @@
+use crate::{StateError, StateEos, StateWarning, eos::Eos};
+
+/// Object safe state of a gas, for equations of state known at runtime.
+///
+/// Unlike [`StateEos`], this trait can be used as a trait object,
+/// to store different kinds of gases in the same collection, e.g. `Vec<Box<dyn DynState>>`.
+/// It is implemented for all the types implementing [`StateEos`].
+/// The methods are prefixed with `dyn_` so that they don't clash with those of [`State`](crate::State)
+/// and [`StateEos`] when the traits are in scope together.
+pub trait DynState {
+    /// The molar mass of the gas, in kg/mol
+    fn dyn_molar_mass(&self) -> f64;
+
+    /// Compute the pressure of the gas for the molar volume and temperature.
+    /// See [`StateEos::pressure_eos`].
+    fn dyn_pressure(&self, eos: Eos, vm: f64, t: f64) -> f64;
+
+    /// Compute the compression factor Z such as Z = PV/RT.
+    /// See [`StateEos::z_eos`].
+    fn dyn_z(&self, eos: Eos, p: f64, t: f64) -> f64;
+
+    /// Compute the compression factor Z such as Z = PV/RT, without panicking.
+    /// See [`StateEos::try_z_eos`].
+    fn dyn_try_z(&self, eos: Eos, p: f64, t: f64) -> Result<(f64, Option<StateWarning>), StateError>;
+
+    /// Compute the molar volume the gas in m^3/mol.
+    /// See [`StateEos::molar_volume_eos`].
+    fn dyn_molar_volume(&self, eos: Eos, p: f64, t: f64) -> f64;
+
+    /// Compute the specific mass of the gas in kg/m^3.
+    /// See [`StateEos::specific_mass_eos`].
+    fn dyn_specific_mass(&self, eos: Eos, p: f64, t: f64) -> f64;
+}
+
+impl<S: StateEos> DynState for S {
+    fn dyn_molar_mass(&self) -> f64 {
+        crate::State::molar_mass(self)
+    }
+
+    fn dyn_pressure(&self, eos: Eos, vm: f64, t: f64) -> f64 {
+        StateEos::pressure_eos(self, eos, vm, t)
+    }
+
+    fn dyn_z(&self, eos: Eos, p: f64, t: f64) -> f64 {
+        StateEos::z_eos(self, eos, p, t)
+    }
+
+    fn dyn_try_z(&self, eos: Eos, p: f64, t: f64) -> Result<(f64, Option<StateWarning>), StateError> {
+        StateEos::try_z_eos(self, eos, p, t)
+    }
+
+    fn dyn_molar_volume(&self, eos: Eos, p: f64, t: f64) -> f64 {
+        StateEos::molar_volume_eos(self, eos, p, t)
+    }
+
+    fn dyn_specific_mass(&self, eos: Eos, p: f64, t: f64) -> f64 {
+        StateEos::specific_mass_eos(self, eos, p, t)
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::DynState;
+    use crate::{State, StateEos, compounds, eos::{Eos, PengRobinson}};
+
+    #[test]
+    fn heterogeneous_gases() {
+        let gases: Vec<Box<dyn DynState>> = vec![
+            Box::new(compounds::CH4),
+            Box::new(compounds::dry_air()),
+            Box::new(compounds::natural_gas()),
+        ];
+        let (p, t) = (100e5, 288.15);
+        let densities: Vec<f64> = gases
+            .iter()
+            .map(|g| g.dyn_specific_mass(Eos::PengRobinson, p, t))
+            .collect();
+
+        assert_eq!(densities[0], compounds::CH4.specific_mass::<PengRobinson>(p, t));
+        assert_eq!(densities[1], compounds::dry_air().specific_mass::<PengRobinson>(p, t));
+        assert_eq!(densities[2], compounds::natural_gas().specific_mass::<PengRobinson>(p, t));
+        for (g, rho) in gases.iter().zip(&densities) {
+            let z = g.dyn_z(Eos::PengRobinson, p, t);
+            assert_eq!(g.dyn_try_z(Eos::PengRobinson, p, t).unwrap().0, z);
+            assert!((g.dyn_molar_mass() / g.dyn_molar_volume(Eos::PengRobinson, p, t) - rho).abs() < 1e-9 * rho);
+        }
+
+        // no ambiguity with all the traits in scope
+        let ch4 = compounds::CH4;
+        assert_eq!(ch4.z_eos(Eos::PengRobinson, p, t), ch4.dyn_z(Eos::PengRobinson, p, t));
+        assert_eq!(ch4.molar_mass(), ch4.dyn_molar_mass());
+    }
+}
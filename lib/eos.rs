@@ -1,10 +1,36 @@
-use std::{borrow::Borrow, fmt, str::FromStr};
+use core::{borrow::Borrow, fmt, str::FromStr};
 
-use crate::{Pvt, R};
+use crate::{Gas, Pvt, R, SolverOptions, math, solver};
+#[cfg(all(not(feature = "std"), not(test)))]
+use crate::math::Float;
+#[cfg(not(feature = "std"))]
+use alloc::{string::{String, ToString}, vec, vec::Vec};
 
 /// The default and recommended equation of state of this library.
 pub type DefaultEos = PengRobinson;
 
+/// R², precomputed for the dimensionless attraction parameters
+const R2: f64 = R * R;
+
+/// The acentric factor above which Patel-Teja-Valderrama is recommended over Peng-Robinson
+const HIGH_ACENTRIC_FACTOR: f64 = 0.3;
+
+/// Recommend an equation of state for `gas`.
+///
+/// Peng-Robinson suits most gases, but Patel-Teja-Valderrama is recommended
+/// for fluids with a high acentric factor, which are often polar, such as water or alcohols.
+pub fn recommended_eos(gas: &Gas) -> Eos {
+    let w = match gas {
+        Gas::Molecule(m) => m.acentric_factor(),
+        Gas::Mixture(m) => m.pseudo_acentric_factor(),
+    };
+    if w > HIGH_ACENTRIC_FACTOR {
+        Eos::PatelTejaValderrama
+    } else {
+        Eos::PengRobinson
+    }
+}
+
 /// The A and B parameters of an equation of state.
 /// Most equations of state use these parameters to compute the pressure of a gas.
 #[derive(Debug, Clone, Copy)]
@@ -27,6 +53,76 @@ pub struct AbcParams {
     pub c: f64,
 }
 
+/// The parameters of an equation of state at given pressure and temperature,
+/// for inspection and validation against hand calculations.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ParamsReport {
+    /// The molecular attraction parameter
+    pub a: f64,
+    /// The molecular volume parameter
+    pub b: f64,
+    /// The additional parameter, for equations that have one
+    pub c: Option<f64>,
+    /// The dimensionless attraction parameter A, e.g. aP/(RT)²
+    pub a_dimensionless: f64,
+    /// The dimensionless volume parameter B = bP/RT
+    pub b_dimensionless: f64,
+}
+
+impl AbParams {
+    fn report(&self, p: f64, t: f64) -> ParamsReport {
+        ParamsReport {
+            a: self.a,
+            b: self.b,
+            c: None,
+            a_dimensionless: self.a * p / (R2 * t * t),
+            b_dimensionless: self.b * p / (R * t),
+        }
+    }
+}
+
+/// The alpha function α(Tr) of the attraction parameter of the Soave-Redlich-Kwong
+/// and Peng-Robinson equations of state.
+///
+/// The coefficients of Mathias-Copeman and Twu-Coon functions are fitted
+/// for a specific compound and equation of state.
+#[derive(Debug, Clone, Copy, Default, PartialEq, PartialOrd)]
+pub enum AlphaFunction {
+    /// α = [1 + m(1 - √Tr)]², where m is correlated to the acentric factor
+    #[default]
+    Standard,
+    /// α = [1 + c1(1 - √Tr) + c2(1 - √Tr)² + c3(1 - √Tr)³]² below Tc,
+    /// and α = [1 + c1(1 - √Tr)]² above Tc (Mathias and Copeman, 1983)
+    MathiasCopeman { c1: f64, c2: f64, c3: f64 },
+    /// α = Tr^(N(M-1)) exp[L(1 - Tr^(NM))] (Twu, Coon and Cunningham, 1995)
+    TwuCoon { l: f64, m: f64, n: f64 },
+}
+
+impl AlphaFunction {
+    /// Compute α at the reduced temperature `tr`.
+    /// `m` is the slope of the standard function, correlated to the acentric factor by each equation.
+    pub fn alpha(&self, m: f64, tr: f64) -> f64 {
+        match *self {
+            AlphaFunction::Standard => {
+                let sq_a = 1f64 + m * (1f64 - tr.sqrt());
+                sq_a * sq_a
+            }
+            AlphaFunction::MathiasCopeman { c1, c2, c3 } => {
+                let x = 1f64 - tr.sqrt();
+                let sq_a = if tr < 1f64 {
+                    1f64 + c1 * x + c2 * x * x + c3 * x * x * x
+                } else {
+                    1f64 + c1 * x
+                };
+                sq_a * sq_a
+            }
+            AlphaFunction::TwuCoon { l, m, n } => {
+                tr.powf(n * (m - 1f64)) * (l * (1f64 - tr.powf(n * m))).exp()
+            }
+        }
+    }
+}
+
 /// Mixing rules for equations of state parameters.
 pub trait MixingRules {
     fn mix<P>(mixture_params: P) -> Self
@@ -41,7 +137,6 @@ impl MixingRules for () {
         P: IntoIterator + Clone,
         P::Item: Borrow<(f64, Self)>,
     {
-        ()
     }
 }
 
@@ -90,6 +185,9 @@ impl MixingRules for AbcParams {
 }
 
 pub trait EquationOfState {
+    /// The name of the equation of state, e.g. "Peng-Robinson"
+    const NAME: &'static str;
+
     /// The parameters of the equation of state
     type Params: MixingRules;
 
@@ -101,6 +199,21 @@ pub trait EquationOfState {
     ///  * `t`  - The temperature of the gas, in K
     fn params(cs: &Pvt, w: f64, t: f64) -> Self::Params;
 
+    /// Compute the parameters of the equation of state with a specific alpha function.
+    ///
+    /// The default implementation ignores `alpha` and calls [`Self::params`].
+    /// Only the equations of state that have an alpha function use it.
+    ///
+    /// # Arguments
+    ///  * `cs`    - The critical state of the molecule
+    ///  * `w`     - The acentric factor of the molecule (no dimension)
+    ///  * `alpha` - The alpha function of the molecule
+    ///  * `t`     - The temperature of the gas, in K
+    fn params_with_alpha(cs: &Pvt, w: f64, alpha: &AlphaFunction, t: f64) -> Self::Params {
+        let _ = alpha;
+        Self::params(cs, w, t)
+    }
+
     /// Compute the gas pressure for given parameters and state.
     ///
     /// # Arguments
@@ -109,6 +222,19 @@ pub trait EquationOfState {
     ///  * `t`      - The temperature of the gas, in K
     fn pressure(params: &Self::Params, vm: f64, t: f64) -> f64;
 
+    /// Compute the derivative of the pressure with respect to the molar volume (∂P/∂Vm)_T.
+    ///
+    /// The default implementation uses a central finite difference of [`Self::pressure`].
+    ///
+    /// # Arguments
+    ///  * `params` - The equation parameters
+    ///  * `vm`     - The molar volume of the gas, in m^3/mol
+    ///  * `t`      - The temperature of the gas, in K
+    fn dpdv(params: &Self::Params, vm: f64, t: f64) -> f64 {
+        let h = vm * 1e-6;
+        (Self::pressure(params, vm + h, t) - Self::pressure(params, vm - h, t)) / (2.0 * h)
+    }
+
     /// The Z polyn [a3, a2, a1, a0] such as `a3*Z^3 + a2*Z^2 + a1*Z + a0 = 0`
     ///
     /// # Arguments
@@ -116,32 +242,109 @@ pub trait EquationOfState {
     ///  * `p`      - The pressure of the gas, in Pa
     ///  * `t`      - The temperature of the gas, in K
     fn z_polyn(params: &Self::Params, p: f64, t: f64) -> [f64; 4];
+
+    /// Report the parameters at given pressure and temperature, for inspection.
+    ///
+    /// Returns `None` for equations that are not expressed with a and b parameters.
+    fn params_report(params: &Self::Params, p: f64, t: f64) -> Option<ParamsReport> {
+        let _ = (params, p, t);
+        None
+    }
+
+    /// The residual molar Helmholtz energy divided by RT, at given molar volume and temperature.
+    ///
+    /// This is `(A - A_ig)/RT` where `A_ig` is the Helmholtz energy of the ideal gas
+    /// at the same temperature and molar volume. It is the basis of fugacity and departure functions.
+    /// The default implementation integrates `(Z - 1)/ρ` over the molar density.
+    ///
+    /// # Arguments
+    ///  * `params` - The equation parameters
+    ///  * `vm`     - The molar volume of the gas, in m^3/mol
+    ///  * `t`      - The temperature of the gas, in K
+    fn residual_helmholtz(params: &Self::Params, vm: f64, t: f64) -> f64 {
+        let f = |rho: f64| (Self::pressure(params, 1.0 / rho, t) / (rho * R * t) - 1.0) / rho;
+        integrate(f, 0.0, 1.0 / vm)
+    }
+
+    /// The second virial coefficient B(T), in m^3/mol, implied by the equation of state.
+    ///
+    /// B(T) is the limit of (Z - 1)·Vm when the molar volume tends to infinity.
+    /// The default implementation extrapolates it from the pressure at two low densities.
+    ///
+    /// # Arguments
+    ///  * `params` - The equation parameters
+    ///  * `t`      - The temperature of the gas, in K
+    fn second_virial(params: &Self::Params, t: f64) -> f64 {
+        // (Z - 1)/rho = B + C.rho + ...
+        let f = |rho: f64| (Self::pressure(params, 1.0 / rho, t) / (rho * R * t) - 1.0) / rho;
+        let rho = 1e-3;
+        2.0 * f(rho) - f(2.0 * rho)
+    }
+
+    /// Compute the compression factor Z for given parameters and state.
+    ///
+    /// The default implementation resolves the polynomial given by [`Self::z_polyn`]
+    /// and selects the highest real root.
+    /// Returns `None` if no positive real root can be found, or if the root gives
+    /// a molar volume below the covolume b (Z ≤ B), which has no physical meaning.
+    ///
+    /// # Arguments
+    ///  * `params` - The equation parameters
+    ///  * `p`      - The pressure of the gas, in Pa
+    ///  * `t`      - The temperature of the gas, in K
+    fn z(params: &Self::Params, p: f64, t: f64) -> Option<f64> {
+        let report = Self::params_report(params, p, t);
+        positive_roots(Self::z_polyn(params, p, t))
+            .last()
+            .copied()
+            .filter(|&z| above_covolume(z, report.as_ref()))
+    }
 }
 
 /// The ideal gas law
 pub enum IdealGas {}
 
 impl EquationOfState for IdealGas {
+    const NAME: &'static str = "Ideal gas";
+
     type Params = ();
     fn params(_cs: &Pvt, _w: f64, _t: f64) -> Self::Params {
         // No parameters needed for the ideal gas law
-        ()
     }
 
     fn pressure(_params: &Self::Params, vm: f64, t: f64) -> f64 {
         R * t / vm
     }
 
+    fn dpdv(_params: &Self::Params, vm: f64, t: f64) -> f64 {
+        -R * t / (vm * vm)
+    }
+
     fn z_polyn(_params: &Self::Params, _p: f64, _t: f64) -> [f64; 4] {
         // Z = 1
         [0.0, 0.0, 1.0, -1.0]
     }
+
+    fn residual_helmholtz(_params: &Self::Params, _vm: f64, _t: f64) -> f64 {
+        0.0
+    }
+
+    fn second_virial(_params: &Self::Params, _t: f64) -> f64 {
+        0.0
+    }
+
+    fn z(_params: &Self::Params, _p: f64, _t: f64) -> Option<f64> {
+        // no need of the cubic solver
+        Some(1.0)
+    }
 }
 
 /// The Van der Waals equation of state
 pub enum VanDerWaals {}
 
 impl EquationOfState for VanDerWaals {
+    const NAME: &'static str = "Van der Waals";
+
     type Params = AbParams;
 
     fn params(cs: &Pvt, _w: f64, _t: f64) -> Self::Params {
@@ -155,8 +358,13 @@ impl EquationOfState for VanDerWaals {
         R * t / (vm - b) - a / (vm * vm)
     }
 
+    fn dpdv(params: &Self::Params, vm: f64, t: f64) -> f64 {
+        let AbParams { a, b } = *params;
+        -R * t / ((vm - b) * (vm - b)) + 2.0 * a / (vm * vm * vm)
+    }
+
     fn z_polyn(params: &Self::Params, p: f64, t: f64) -> [f64; 4] {
-        let a = params.a * p / (R * R * t * t);
+        let a = params.a * p / (R2 * t * t);
         let b = params.b * p / (R * t);
 
         let a3 = 1f64;
@@ -166,12 +374,130 @@ impl EquationOfState for VanDerWaals {
 
         [a3, a2, a1, a0]
     }
+
+    fn params_report(params: &Self::Params, p: f64, t: f64) -> Option<ParamsReport> {
+        Some(params.report(p, t))
+    }
+
+    fn residual_helmholtz(params: &Self::Params, vm: f64, t: f64) -> f64 {
+        let AbParams { a, b } = *params;
+        -(1.0 - b / vm).ln() - a / (R * t * vm)
+    }
+
+    fn second_virial(params: &Self::Params, t: f64) -> f64 {
+        params.b - params.a / (R * t)
+    }
+}
+
+/// The Clausius equation of state (1880).
+///
+/// P = RT/(Vm - b) - a/(T(Vm + c)²), where the three parameters are fitted
+/// to the critical temperature, pressure and molar volume.
+pub enum Clausius {}
+
+impl EquationOfState for Clausius {
+    const NAME: &'static str = "Clausius";
+
+    /// The temperature is included in `a`
+    type Params = AbcParams;
+
+    fn params(cs: &Pvt, _w: f64, t: f64) -> Self::Params {
+        let a = 27.0 * R * R * cs.t * cs.t * cs.t / (64.0 * cs.p * t);
+        let b = cs.v - R * cs.t / (4.0 * cs.p);
+        let c = 3.0 * R * cs.t / (8.0 * cs.p) - cs.v;
+        AbcParams { a, b, c }
+    }
+
+    fn pressure(params: &Self::Params, vm: f64, t: f64) -> f64 {
+        let AbcParams { a, b, c } = *params;
+        R * t / (vm - b) - a / ((vm + c) * (vm + c))
+    }
+
+    fn dpdv(params: &Self::Params, vm: f64, t: f64) -> f64 {
+        let AbcParams { a, b, c } = *params;
+        -R * t / ((vm - b) * (vm - b)) + 2.0 * a / ((vm + c) * (vm + c) * (vm + c))
+    }
+
+    fn z_polyn(params: &Self::Params, p: f64, t: f64) -> [f64; 4] {
+        let a = params.a * p / (R2 * t * t);
+        let b = params.b * p / (R * t);
+        let c = params.c * p / (R * t);
+
+        let a3 = 1f64;
+        let a2 = 2f64 * c - b - 1f64;
+        let a1 = a + c * c - 2f64 * b * c - 2f64 * c;
+        let a0 = -a * b - b * c * c - c * c;
+
+        [a3, a2, a1, a0]
+    }
+
+    fn params_report(params: &Self::Params, p: f64, t: f64) -> Option<ParamsReport> {
+        let AbcParams { a, b, c } = *params;
+        Some(ParamsReport {
+            c: Some(c),
+            ..AbParams { a, b }.report(p, t)
+        })
+    }
+
+    fn residual_helmholtz(params: &Self::Params, vm: f64, t: f64) -> f64 {
+        let AbcParams { a, b, c } = *params;
+        -(1.0 - b / vm).ln() - a / (R * t * (vm + c))
+    }
+
+    fn second_virial(params: &Self::Params, t: f64) -> f64 {
+        params.b - params.a / (R * t)
+    }
+}
+
+/// The Berthelot equation of state (1899).
+///
+/// P = RT/(Vm - b) - a/(T·Vm²), that is Van der Waals with an attraction
+/// decreasing with the temperature.
+pub enum Berthelot {}
+
+impl EquationOfState for Berthelot {
+    const NAME: &'static str = "Berthelot";
+
+    /// The temperature is included in `a`
+    type Params = AbParams;
+
+    fn params(cs: &Pvt, _w: f64, t: f64) -> Self::Params {
+        let a = 27.0 * R * R * cs.t * cs.t * cs.t / (64.0 * cs.p * t);
+        let b = R * cs.t / (8.0 * cs.p);
+        AbParams { a, b }
+    }
+
+    fn pressure(params: &Self::Params, vm: f64, t: f64) -> f64 {
+        VanDerWaals::pressure(params, vm, t)
+    }
+
+    fn dpdv(params: &Self::Params, vm: f64, t: f64) -> f64 {
+        VanDerWaals::dpdv(params, vm, t)
+    }
+
+    fn z_polyn(params: &Self::Params, p: f64, t: f64) -> [f64; 4] {
+        VanDerWaals::z_polyn(params, p, t)
+    }
+
+    fn params_report(params: &Self::Params, p: f64, t: f64) -> Option<ParamsReport> {
+        VanDerWaals::params_report(params, p, t)
+    }
+
+    fn residual_helmholtz(params: &Self::Params, vm: f64, t: f64) -> f64 {
+        VanDerWaals::residual_helmholtz(params, vm, t)
+    }
+
+    fn second_virial(params: &Self::Params, t: f64) -> f64 {
+        VanDerWaals::second_virial(params, t)
+    }
 }
 
 /// The Redlich-Kwong equation of state
 pub enum RedlichKwong {}
 
 impl EquationOfState for RedlichKwong {
+    const NAME: &'static str = "Redlich-Kwong";
+
     type Params = AbParams;
 
     fn params(cs: &Pvt, _w: f64, _t: f64) -> Self::Params {
@@ -186,8 +512,14 @@ impl EquationOfState for RedlichKwong {
         R * t / (vm - b) - a / (t.sqrt() * vm * (vm + b))
     }
 
+    fn dpdv(params: &Self::Params, vm: f64, t: f64) -> f64 {
+        let AbParams { a, b } = *params;
+        let d = vm * (vm + b);
+        -R * t / ((vm - b) * (vm - b)) + a * (2.0 * vm + b) / (t.sqrt() * d * d)
+    }
+
     fn z_polyn(params: &Self::Params, p: f64, t: f64) -> [f64; 4] {
-        let a = params.a * p / (R * R * t.powf(2.5));
+        let a = params.a * p / (R2 * t.powf(2.5));
         let b = params.b * p / (R * t);
 
         let a3 = 1f64;
@@ -197,18 +529,39 @@ impl EquationOfState for RedlichKwong {
 
         [a3, a2, a1, a0]
     }
+
+    fn params_report(params: &Self::Params, p: f64, t: f64) -> Option<ParamsReport> {
+        Some(ParamsReport {
+            a_dimensionless: params.a * p / (R2 * t.powf(2.5)),
+            ..params.report(p, t)
+        })
+    }
+
+    fn residual_helmholtz(params: &Self::Params, vm: f64, t: f64) -> f64 {
+        let AbParams { a, b } = *params;
+        -(1.0 - b / vm).ln() - a / (b * R * t.powf(1.5)) * (1.0 + b / vm).ln()
+    }
+
+    fn second_virial(params: &Self::Params, t: f64) -> f64 {
+        params.b - params.a / (R * t.powf(1.5))
+    }
 }
 
 /// The Soave-Redlich-Kwong equation of state
 pub enum SoaveRedlichKwong {}
 
 impl EquationOfState for SoaveRedlichKwong {
+    const NAME: &'static str = "Soave-Redlich-Kwong";
+
     type Params = AbParams;
 
     fn params(cs: &Pvt, w: f64, t: f64) -> Self::Params {
+        Self::params_with_alpha(cs, w, &AlphaFunction::Standard, t)
+    }
+
+    fn params_with_alpha(cs: &Pvt, w: f64, alpha: &AlphaFunction, t: f64) -> Self::Params {
         let m = 0.48 + 1.574 * w - 0.176 * w * w;
-        let sq_a = 1f64 + m * (1f64 - (t / cs.t).sqrt());
-        let alpha = sq_a * sq_a;
+        let alpha = alpha.alpha(m, t / cs.t);
 
         let a = alpha * 0.42748023 * R * R * cs.t * cs.t / cs.p;
         let b = 0.08664035 * R * cs.t / cs.p;
@@ -221,8 +574,14 @@ impl EquationOfState for SoaveRedlichKwong {
         R * t / (vm - b) - a / (vm * (vm + b))
     }
 
+    fn dpdv(params: &Self::Params, vm: f64, t: f64) -> f64 {
+        let AbParams { a, b } = *params;
+        let d = vm * (vm + b);
+        -R * t / ((vm - b) * (vm - b)) + a * (2.0 * vm + b) / (d * d)
+    }
+
     fn z_polyn(params: &Self::Params, p: f64, t: f64) -> [f64; 4] {
-        let a = params.a * p / (R * R * t * t);
+        let a = params.a * p / (R2 * t * t);
         let b = params.b * p / (R * t);
 
         let a3 = 1f64;
@@ -232,27 +591,40 @@ impl EquationOfState for SoaveRedlichKwong {
 
         [a3, a2, a1, a0]
     }
+
+    fn params_report(params: &Self::Params, p: f64, t: f64) -> Option<ParamsReport> {
+        Some(params.report(p, t))
+    }
+
+    fn residual_helmholtz(params: &Self::Params, vm: f64, t: f64) -> f64 {
+        let AbParams { a, b } = *params;
+        -(1.0 - b / vm).ln() - a / (b * R * t) * (1.0 + b / vm).ln()
+    }
+
+    fn second_virial(params: &Self::Params, t: f64) -> f64 {
+        params.b - params.a / (R * t)
+    }
 }
 
-/// The Peng-Robinson equation of state
+/// The Peng-Robinson equation of state, with the 1978 correlation of the alpha function
 pub enum PengRobinson {}
 
 impl EquationOfState for PengRobinson {
+    const NAME: &'static str = "Peng-Robinson";
+
     type Params = AbParams;
 
     fn params(cs: &Pvt, w: f64, t: f64) -> Self::Params {
+        Self::params_with_alpha(cs, w, &AlphaFunction::Standard, t)
+    }
+
+    fn params_with_alpha(cs: &Pvt, w: f64, alpha: &AlphaFunction, t: f64) -> Self::Params {
         let m = if w <= 0.491 {
             0.37464 + 1.56226 * w - 0.26992 * w * w
         } else {
             0.379642 + 1.487503 * w - 0.164423 * w * w - 0.016666 * w * w * w
         };
-        let sq_a = 1f64 + m * (1f64 - (t / cs.t).sqrt());
-        let alpha = sq_a * sq_a;
-
-        let a = alpha * 0.4572355289213821 * R * R * cs.t * cs.t / cs.p;
-        let b = 0.07779607390388844 * R * cs.t / cs.p;
-
-        AbParams { a, b }
+        peng_robinson_params(cs, m, alpha, t)
     }
 
     fn pressure(params: &Self::Params, vm: f64, t: f64) -> f64 {
@@ -260,22 +632,101 @@ impl EquationOfState for PengRobinson {
         R * t / (vm - b) - a / (vm * vm + 2.0 * b * vm - b * b)
     }
 
+    fn dpdv(params: &Self::Params, vm: f64, t: f64) -> f64 {
+        let AbParams { a, b } = *params;
+        let d = vm * vm + 2.0 * b * vm - b * b;
+        -R * t / ((vm - b) * (vm - b)) + a * (2.0 * vm + 2.0 * b) / (d * d)
+    }
+
     fn z_polyn(params: &Self::Params, p: f64, t: f64) -> [f64; 4] {
-        let a = params.a * p / (R * R * t * t);
+        let a = params.a * p / (R2 * t * t);
         let b = params.b * p / (R * t);
 
         let a3 = 1f64;
         let a2 = b - 1f64;
+        let b2 = b * b;
         let a1 = -3f64 * b * b - 2f64 * b + a;
-        let a0 = b * b * b + b * b - a * b;
+        let a0 = b2 * b + b2 - a * b;
 
         [a3, a2, a1, a0]
     }
+
+    fn params_report(params: &Self::Params, p: f64, t: f64) -> Option<ParamsReport> {
+        Some(params.report(p, t))
+    }
+
+    fn residual_helmholtz(params: &Self::Params, vm: f64, t: f64) -> f64 {
+        let AbParams { a, b } = *params;
+        let sq2 = core::f64::consts::SQRT_2;
+        -(1.0 - b / vm).ln()
+            - a / (2.0 * sq2 * b * R * t) * ((vm + (1.0 + sq2) * b) / (vm + (1.0 - sq2) * b)).ln()
+    }
+
+    fn second_virial(params: &Self::Params, t: f64) -> f64 {
+        params.b - params.a / (R * t)
+    }
+}
+
+/// The Peng-Robinson parameters for the alpha function slope `m`
+fn peng_robinson_params(cs: &Pvt, m: f64, alpha: &AlphaFunction, t: f64) -> AbParams {
+    let alpha = alpha.alpha(m, t / cs.t);
+
+    let a = alpha * 0.4572355289213821 * R * R * cs.t * cs.t / cs.p;
+    let b = 0.07779607390388844 * R * cs.t / cs.p;
+
+    AbParams { a, b }
+}
+
+/// The Peng-Robinson equation of state, with the original 1976 correlation of the alpha function.
+///
+/// It only differs from [`PengRobinson`] by the alpha function, and is mostly useful
+/// to reproduce older results.
+pub enum PengRobinson1976 {}
+
+impl EquationOfState for PengRobinson1976 {
+    const NAME: &'static str = "Peng-Robinson 1976";
+
+    type Params = AbParams;
+
+    fn params(cs: &Pvt, w: f64, t: f64) -> Self::Params {
+        Self::params_with_alpha(cs, w, &AlphaFunction::Standard, t)
+    }
+
+    fn params_with_alpha(cs: &Pvt, w: f64, alpha: &AlphaFunction, t: f64) -> Self::Params {
+        let m = 0.37464 + 1.54226 * w - 0.26992 * w * w;
+        peng_robinson_params(cs, m, alpha, t)
+    }
+
+    fn pressure(params: &Self::Params, vm: f64, t: f64) -> f64 {
+        PengRobinson::pressure(params, vm, t)
+    }
+
+    fn dpdv(params: &Self::Params, vm: f64, t: f64) -> f64 {
+        PengRobinson::dpdv(params, vm, t)
+    }
+
+    fn z_polyn(params: &Self::Params, p: f64, t: f64) -> [f64; 4] {
+        PengRobinson::z_polyn(params, p, t)
+    }
+
+    fn params_report(params: &Self::Params, p: f64, t: f64) -> Option<ParamsReport> {
+        PengRobinson::params_report(params, p, t)
+    }
+
+    fn residual_helmholtz(params: &Self::Params, vm: f64, t: f64) -> f64 {
+        PengRobinson::residual_helmholtz(params, vm, t)
+    }
+
+    fn second_virial(params: &Self::Params, t: f64) -> f64 {
+        PengRobinson::second_virial(params, t)
+    }
 }
 
 pub enum PatelTejaValderrama {}
 
 impl EquationOfState for PatelTejaValderrama {
+    const NAME: &'static str = "Patel-Teja-Valderrama";
+
     type Params = AbcParams;
 
     fn params(cs: &Pvt, w: f64, t: f64) -> Self::Params {
@@ -301,40 +752,538 @@ impl EquationOfState for PatelTejaValderrama {
         R * t / (vm - b) - a / (vm * (vm + b) + c * (vm - b))
     }
 
+    fn dpdv(params: &Self::Params, vm: f64, t: f64) -> f64 {
+        let AbcParams { a, b, c } = *params;
+        let d = vm * (vm + b) + c * (vm - b);
+        -R * t / ((vm - b) * (vm - b)) + a * (2.0 * vm + b + c) / (d * d)
+    }
+
     fn z_polyn(params: &Self::Params, p: f64, t: f64) -> [f64; 4] {
-        let a = params.a * p / (R * R * t * t);
-        let b = params.b * p / (R * t);
-        let c = params.c * p / (R * t);
+        let rt = R * t;
+        let a = params.a * p / (R2 * t * t);
+        let b = params.b * p / rt;
+        let c = params.c * p / rt;
 
         let a3 = 1f64;
         let a2 = c - 1f64;
-        let a1 = -2f64 * b * c - b * b - b - c + a;
-        let a0 = b * b * c + b * c - a * b;
+        let b2 = b * b;
+        let a1 = -2f64 * b * c - b2 - b - c + a;
+        let a0 = b2 * c + b * c - a * b;
 
         [a3, a2, a1, a0]
     }
+
+    fn params_report(params: &Self::Params, p: f64, t: f64) -> Option<ParamsReport> {
+        let AbcParams { a, b, c } = *params;
+        Some(ParamsReport {
+            c: Some(c),
+            ..AbParams { a, b }.report(p, t)
+        })
+    }
+
+    fn residual_helmholtz(params: &Self::Params, vm: f64, t: f64) -> f64 {
+        let AbcParams { a, b, c } = *params;
+        // the attraction denominator is (vm - r1)(vm - r2)
+        let sq = ((b + c) * (b + c) + 4.0 * b * c).sqrt();
+        let r1 = (-(b + c) + sq) / 2.0;
+        let r2 = (-(b + c) - sq) / 2.0;
+        -(1.0 - b / vm).ln() - a / (R * t * (r1 - r2)) * ((vm - r2) / (vm - r1)).ln()
+    }
+
+    fn second_virial(params: &Self::Params, t: f64) -> f64 {
+        params.b - params.a / (R * t)
+    }
 }
 
-/// An equation of state determined at runtime
+/// Integrate `f` from `a` to `b` with a 5 points Gauss-Legendre quadrature over 16 panels
+pub(crate) fn integrate<F: Fn(f64) -> f64>(f: F, a: f64, b: f64) -> f64 {
+    const NODES: [f64; 5] = [
+        -0.906179845938664,
+        -0.5384693101056831,
+        0.0,
+        0.5384693101056831,
+        0.906179845938664,
+    ];
+    const WEIGHTS: [f64; 5] = [
+        0.2369268850561891,
+        0.4786286704993665,
+        0.5688888888888889,
+        0.4786286704993665,
+        0.2369268850561891,
+    ];
+    const PANELS: usize = 16;
+
+    let h = (b - a) / PANELS as f64;
+    let mut res = 0.0;
+    for i in 0..PANELS {
+        let mid = a + (i as f64 + 0.5) * h;
+        for (x, w) in NODES.iter().zip(WEIGHTS.iter()) {
+            res += w * f(mid + x * h / 2.0);
+        }
+    }
+    res * h / 2.0
+}
+
+/// The positive roots of the Z polynomial, in increasing order,
+/// without the roots below the covolume
+pub(crate) fn z_roots<E: EquationOfState>(params: &E::Params, p: f64, t: f64) -> Vec<f64> {
+    let report = E::params_report(params, p, t);
+    let mut roots = positive_roots(E::z_polyn(params, p, t));
+    roots.retain(|&z| above_covolume(z, report.as_ref()));
+    roots
+}
+
+/// Whether the Z polynomial has positive roots, all below the covolume
+pub(crate) fn below_covolume<E: EquationOfState>(params: &E::Params, p: f64, t: f64) -> bool {
+    let roots = positive_roots(E::z_polyn(params, p, t));
+    !roots.is_empty() && z_roots::<E>(params, p, t).is_empty()
+}
+
+/// Whether the root `z` gives a molar volume above the covolume b, that is Z > B.
+/// Always true for the equations of state that don't report B.
+fn above_covolume(z: f64, report: Option<&ParamsReport>) -> bool {
+    report.is_none_or(|r| z > r.b_dimensionless)
+}
+
+/// The real positive roots of the cubic polynomial, in increasing order.
+///
+/// Each root is polished with Newton iterations, as the closed form solution
+/// loses precision when the constant coefficient is very small (e.g. at low pressure).
+/// If the leading coefficient is negligible, the polynomial is solved as a quadratic (or linear)
+/// equation, dropping the huge unphysical roots of the degenerate cubic.
+fn positive_roots(polyn: [f64; 4]) -> Vec<f64> {
+    let [a3, a2, a1, a0] = polyn;
+    let scale = a2.abs().max(a1.abs()).max(a0.abs());
+    let roots = if a3.abs() <= 1e-12 * scale {
+        math::find_roots_quadratic(a2, a1, a0)
+    } else {
+        math::find_roots_cubic(a3, a2, a1, a0)
+    };
+    let mut roots = roots.as_ref().to_vec();
+    for r in roots.iter_mut() {
+        *r = newton_polish(polyn, *r);
+    }
+    roots.retain(|&z| z > 0.0);
+    roots.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    roots
+}
+
+/// Refine a root of the cubic polynomial with Newton iterations.
+/// The refined root is only kept if it reduces the residual.
+fn newton_polish(polyn: [f64; 4], root: f64) -> f64 {
+    let [a3, a2, a1, a0] = polyn;
+    let f = |x: f64| ((a3 * x + a2) * x + a1) * x + a0;
+    let df = |x: f64| (3.0 * a3 * x + 2.0 * a2) * x + a1;
+
+    let mut x = root;
+    for _ in 0..8 {
+        let d = df(x);
+        if d == 0.0 {
+            break;
+        }
+        let dx = f(x) / d;
+        x -= dx;
+        if dx.abs() <= f64::EPSILON * x.abs() {
+            break;
+        }
+    }
+    if f(x).abs() <= f(root).abs() { x } else { root }
+}
+
+/// The parameters of the Lee-Kesler correlation.
+/// These are the (pseudo-)critical properties of the gas,
+/// and the convergence control of the iterative resolution of the correlation.
+#[derive(Debug, Clone, Copy)]
+pub struct LeeKeslerParams {
+    /// The (pseudo-)critical temperature, in K
+    pub tc: f64,
+    /// The (pseudo-)critical pressure, in Pa
+    pub pc: f64,
+    /// The (pseudo-)acentric factor
+    pub w: f64,
+    /// The convergence control of the reduced volume and pressure solvers
+    pub options: SolverOptions,
+}
+
+/// Mixing rules of Lee and Kesler for the pseudo-critical properties.
+impl MixingRules for LeeKeslerParams {
+    fn mix<P>(mixture_params: P) -> Self
+    where
+        P: IntoIterator + Clone,
+        P::Item: Borrow<(f64, Self)>,
+    {
+        // critical volume of each component estimated from its acentric factor
+        let vc = |p: &LeeKeslerParams| (0.2905 - 0.085 * p.w) * R * p.tc / p.pc;
+
+        let mut vcm = 0.0;
+        let mut tcm = 0.0;
+        let mut w = 0.0;
+        for params in mixture_params.clone() {
+            let (fi, pi) = params.borrow();
+            for params in mixture_params.clone() {
+                let (fj, pj) = params.borrow();
+                let v = (vc(pi).cbrt() + vc(pj).cbrt()).powi(3) / 8.0;
+                vcm += fi * fj * v;
+                tcm += fi * fj * v * (pi.tc * pj.tc).sqrt();
+            }
+            w += fi * pi.w;
+        }
+        let tc = tcm / vcm;
+        let pc = (0.2905 - 0.085 * w) * R * tc / vcm;
+        LeeKeslerParams {
+            tc,
+            pc,
+            w,
+            options: SolverOptions::default(),
+        }
+    }
+}
+
+/// The constants of a fluid of the Lee-Kesler correlation
+struct LeeKeslerFluid {
+    b: [f64; 4],
+    c: [f64; 4],
+    d: [f64; 2],
+    beta: f64,
+    gamma: f64,
+}
+
+impl LeeKeslerFluid {
+    /// The B coefficient of the fluid as a function of reduced temperature
+    fn b_tr(&self, tr: f64) -> f64 {
+        let [b1, b2, b3, b4] = self.b;
+        b1 - b2 / tr - b3 / (tr * tr) - b4 / (tr * tr * tr)
+    }
+
+    /// Compression factor of the fluid as a function of reduced temperature and reduced volume
+    fn z_trvr(&self, tr: f64, vr: f64) -> f64 {
+        let [c1, c2, c3, c4] = self.c;
+        let [d1, d2] = self.d;
+
+        let b = self.b_tr(tr);
+        let c = c1 - c2 / tr + c3 / (tr * tr * tr);
+        let d = d1 + d2 / tr;
+        let vr2 = vr * vr;
+
+        1.0 + b / vr + c / vr2 + d / (vr2 * vr2 * vr)
+            + c4 / (tr * tr * tr * vr2) * (self.beta + self.gamma / vr2) * (-self.gamma / vr2).exp()
+    }
+
+    /// Compression factor of the fluid as a function of reduced temperature and reduced pressure.
+    /// The root of highest reduced volume is selected.
+    fn z_trpr(&self, tr: f64, pr: f64, options: &SolverOptions) -> Option<f64> {
+        let f = |vr: f64| pr * vr / tr - self.z_trvr(tr, vr);
+
+        // start from twice the ideal gas volume, and scan downwards until a sign change
+        let mut hi = 2.0 * tr / pr;
+        while f(hi) <= 0.0 {
+            hi *= 2.0;
+            if !hi.is_finite() {
+                return None;
+            }
+        }
+        let mut lo = hi;
+        loop {
+            hi = lo;
+            lo *= 0.97;
+            if f(lo) < 0.0 {
+                break;
+            }
+            if lo < 1e-3 {
+                return None;
+            }
+        }
+
+        let vr = solver::find_root_brent(lo, hi, f, hi, options).ok()?;
+        Some(pr * vr / tr)
+    }
+}
+
+/// The simple fluid of the Lee-Kesler correlation
+const LEE_KESLER_SIMPLE: LeeKeslerFluid = LeeKeslerFluid {
+    b: [0.1181193, 0.265728, 0.154790, 0.030323],
+    c: [0.0236744, 0.0186984, 0.0, 0.042724],
+    d: [0.155488e-4, 0.623689e-4],
+    beta: 0.65392,
+    gamma: 0.060167,
+};
+
+/// The reference fluid (n-octane) of the Lee-Kesler correlation
+const LEE_KESLER_REFERENCE: LeeKeslerFluid = LeeKeslerFluid {
+    b: [0.2026579, 0.331511, 0.027655, 0.203488],
+    c: [0.0313385, 0.0503618, 0.016901, 0.041577],
+    d: [0.48736e-4, 0.0740336e-5],
+    beta: 1.226,
+    gamma: 0.03754,
+};
+
+/// The acentric factor of the reference fluid of the Lee-Kesler correlation
+const LEE_KESLER_REFERENCE_W: f64 = 0.3978;
+
+/// The Lee-Kesler generalized correlation.
+///
+/// This is not a cubic equation of state: Z is interpolated between a simple fluid
+/// and a reference fluid, each described by a modified Benedict-Webb-Rubin equation
+/// in reduced coordinates. It is widely used as an accuracy benchmark for the cubic equations.
+///
+/// As there is no cubic polynomial, [`EquationOfState::z_polyn`] returns the degenerate
+/// polynomial `Z - z = 0`, and the pressure is resolved iteratively.
+pub enum LeeKesler {}
+
+impl EquationOfState for LeeKesler {
+    const NAME: &'static str = "Lee-Kesler";
+
+    type Params = LeeKeslerParams;
+
+    fn params(cs: &Pvt, w: f64, _t: f64) -> Self::Params {
+        LeeKeslerParams {
+            tc: cs.t,
+            pc: cs.p,
+            w,
+            options: SolverOptions::default(),
+        }
+    }
+
+    fn pressure(params: &Self::Params, vm: f64, t: f64) -> f64 {
+        // solve p such as p.vm = Z(p).R.t
+        let f = |p: f64| match Self::z(params, p, t) {
+            Some(z) => p - z * R * t / vm,
+            None => f64::NAN,
+        };
+
+        let mut hi = R * t / vm;
+        while f(hi) <= 0.0 {
+            hi *= 2.0;
+            if !hi.is_finite() {
+                return f64::NAN;
+            }
+        }
+        solver::find_root_brent(0.0, hi, f, hi, &params.options).unwrap_or(f64::NAN)
+    }
+
+    fn z_polyn(params: &Self::Params, p: f64, t: f64) -> [f64; 4] {
+        let z = Self::z(params, p, t).unwrap_or(f64::NAN);
+        [0.0, 0.0, 1.0, -z]
+    }
+
+    fn second_virial(params: &Self::Params, t: f64) -> f64 {
+        // only the B/Vr term of the correlation contributes
+        let tr = t / params.tc;
+        let b0 = LEE_KESLER_SIMPLE.b_tr(tr);
+        let br = LEE_KESLER_REFERENCE.b_tr(tr);
+        let b = b0 + params.w / LEE_KESLER_REFERENCE_W * (br - b0);
+        b * R * params.tc / params.pc
+    }
+
+    fn z(params: &Self::Params, p: f64, t: f64) -> Option<f64> {
+        if p == 0.0 {
+            return Some(1.0);
+        }
+        lee_kesler_z(t / params.tc, p / params.pc, params.w, &params.options)
+    }
+}
+
+fn lee_kesler_z(tr: f64, pr: f64, w: f64, options: &SolverOptions) -> Option<f64> {
+    let z0 = LEE_KESLER_SIMPLE.z_trpr(tr, pr, options)?;
+    let zr = LEE_KESLER_REFERENCE.z_trpr(tr, pr, options)?;
+    Some(z0 + w / LEE_KESLER_REFERENCE_W * (zr - z0))
+}
+
+/// The compression factor from the corresponding states principle,
+/// as a function of reduced temperature `tr`, reduced pressure `pr` and acentric factor `w`.
+///
+/// This is the two-parameter correlation of Pitzer Z = Z0(Tr, Pr) + ω·Z1(Tr, Pr),
+/// with Z0 and Z1 given by the analytical fit of Lee and Kesler (1975) instead of tables.
+/// It is a quick estimate, independent of any gas data other than ω,
+/// that reproduces the generalized compressibility charts of Nelson and Obert.
+/// Returns NaN if the correlation has no solution.
+pub fn z_corresponding_states(tr: f64, pr: f64, w: f64) -> f64 {
+    if pr == 0.0 {
+        return 1.0;
+    }
+    lee_kesler_z(tr, pr, w, &SolverOptions::default()).unwrap_or(f64::NAN)
+}
+
+/// An equation of state defined at runtime, used as a trait object.
+///
+/// Unlike [`EquationOfState`], this trait is dynamically dispatched,
+/// which allows equations of state that are not known at compile time (see [`CustomCubic`]).
+/// The tradeoff is a virtual call per evaluation, and the parameters are
+/// restricted to [`AbParams`] with their standard mixing rules.
+pub trait DynEos {
+    /// The name of the equation of state
+    fn name(&self) -> &str;
+
+    /// Compute the parameters of the equation of state for a molecule
+    fn params(&self, cs: &Pvt, w: f64, t: f64) -> AbParams;
+
+    /// Compute the pressure of the gas for the molar volume and temperature
+    fn pressure(&self, params: &AbParams, vm: f64, t: f64) -> f64;
+
+    /// The coefficients of the cubic polynomial of Z, from the highest degree
+    fn z_polyn(&self, params: &AbParams, p: f64, t: f64) -> [f64; 4];
+
+    /// Compute the compression factor Z, choosing the largest positive root of the polynomial.
+    /// Returns `None` if the root is below the covolume (Z ≤ B).
+    fn z(&self, params: &AbParams, p: f64, t: f64) -> Option<f64> {
+        let report = params.report(p, t);
+        positive_roots(self.z_polyn(params, p, t))
+            .last()
+            .copied()
+            .filter(|&z| above_covolume(z, Some(&report)))
+    }
+}
+
+/// A cubic equation of state defined with user supplied functions.
+///
+/// This allows to experiment with a cubic variant without forking the crate.
+/// It implements [`DynEos`] (and not [`EquationOfState`]) and is used with the `*_dyn`
+/// methods of [`StateEos`](crate::StateEos).
 #[derive(Debug, Clone, Copy)]
+pub struct CustomCubic {
+    /// The name of the equation of state
+    pub name: &'static str,
+    /// Compute the parameters from the critical state, acentric factor and temperature
+    pub params: fn(cs: &Pvt, w: f64, t: f64) -> AbParams,
+    /// Compute the pressure from the parameters, molar volume and temperature
+    pub pressure: fn(params: &AbParams, vm: f64, t: f64) -> f64,
+    /// Compute the Z polynomial from the parameters, pressure and temperature
+    pub z_polyn: fn(params: &AbParams, p: f64, t: f64) -> [f64; 4],
+}
+
+impl DynEos for CustomCubic {
+    fn name(&self) -> &str {
+        self.name
+    }
+
+    fn params(&self, cs: &Pvt, w: f64, t: f64) -> AbParams {
+        (self.params)(cs, w, t)
+    }
+
+    fn pressure(&self, params: &AbParams, vm: f64, t: f64) -> f64 {
+        (self.pressure)(params, vm, t)
+    }
+
+    fn z_polyn(&self, params: &AbParams, p: f64, t: f64) -> [f64; 4] {
+        (self.z_polyn)(params, p, t)
+    }
+}
+
+/// The molar fraction, critical state and acentric factor of each molecule of a gas
+#[derive(Debug, Clone)]
+pub(crate) struct Components(pub(crate) Vec<(f64, Pvt, f64)>);
+
+impl MixingRules for Components {
+    fn mix<P>(mixture_params: P) -> Self
+    where
+        P: IntoIterator + Clone,
+        P::Item: Borrow<(f64, Self)>,
+    {
+        let mut comps = Vec::new();
+        for params in mixture_params {
+            let (f, Components(c)) = params.borrow();
+            comps.extend(c.iter().map(|(fi, cs, w)| (f * fi, *cs, *w)));
+        }
+        Components(comps)
+    }
+}
+
+/// A pseudo equation of state whose parameters are the components of the gas.
+/// Used to compute the parameters of a [`DynEos`] from any [`State`](crate::State).
+pub(crate) enum CollectComponents {}
+
+impl EquationOfState for CollectComponents {
+    const NAME: &'static str = "Components";
+
+    type Params = Components;
+
+    fn params(cs: &Pvt, w: f64, _t: f64) -> Self::Params {
+        Components(vec![(1.0, *cs, w)])
+    }
+
+    fn pressure(_params: &Self::Params, _vm: f64, _t: f64) -> f64 {
+        unreachable!("only used to collect the components of a gas")
+    }
+
+    fn z_polyn(_params: &Self::Params, _p: f64, _t: f64) -> [f64; 4] {
+        unreachable!("only used to collect the components of a gas")
+    }
+}
+
+/// An equation of state determined at runtime
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Eos {
     /// The ideal gas law
     IdealGas,
     /// The Van der Waals equation of state
     VanDerWaals,
+    /// The Clausius equation of state
+    Clausius,
+    /// The Berthelot equation of state
+    Berthelot,
     /// The Redlich-Kwong equation of state
     RedlichKwong,
     /// The Soave-Redlich-Kwong equation of state
     SoaveRedlichKwong,
     /// The Peng-Robinson equation of state
+    #[default]
     PengRobinson,
+    /// The Peng-Robinson equation of state, with the original 1976 alpha function
+    PengRobinson1976,
     /// The Patel-Teja-Valderrama equation of state
     PatelTejaValderrama,
+    /// The Lee-Kesler generalized correlation
+    LeeKesler,
 }
 
-impl Default for Eos {
-    fn default() -> Self {
-        Eos::PengRobinson
+impl Eos {
+    /// All the equations of state
+    pub const fn all() -> &'static [Eos] {
+        &[
+            Eos::IdealGas,
+            Eos::VanDerWaals,
+            Eos::Clausius,
+            Eos::Berthelot,
+            Eos::RedlichKwong,
+            Eos::SoaveRedlichKwong,
+            Eos::PengRobinson,
+            Eos::PengRobinson1976,
+            Eos::PatelTejaValderrama,
+            Eos::LeeKesler,
+        ]
+    }
+
+    /// The short code of the equation, as parsed by `Eos::from_str` (case insensitive)
+    pub const fn code(self) -> &'static str {
+        match self {
+            Eos::IdealGas => "ideal",
+            Eos::VanDerWaals => "vdw",
+            Eos::Clausius => "clausius",
+            Eos::Berthelot => "berthelot",
+            Eos::RedlichKwong => "rk",
+            Eos::SoaveRedlichKwong => "srk",
+            Eos::PengRobinson => "pr",
+            Eos::PengRobinson1976 => "pr76",
+            Eos::PatelTejaValderrama => "ptv",
+            Eos::LeeKesler => "lk",
+        }
+    }
+
+    /// The name of the equation
+    pub const fn name(self) -> &'static str {
+        match self {
+            Eos::IdealGas => IdealGas::NAME,
+            Eos::VanDerWaals => VanDerWaals::NAME,
+            Eos::Clausius => Clausius::NAME,
+            Eos::Berthelot => Berthelot::NAME,
+            Eos::RedlichKwong => RedlichKwong::NAME,
+            Eos::SoaveRedlichKwong => SoaveRedlichKwong::NAME,
+            Eos::PengRobinson => PengRobinson::NAME,
+            Eos::PengRobinson1976 => PengRobinson1976::NAME,
+            Eos::PatelTejaValderrama => PatelTejaValderrama::NAME,
+            Eos::LeeKesler => LeeKesler::NAME,
+        }
     }
 }
 
@@ -347,19 +1296,419 @@ impl fmt::Display for ParseEosError {
     }
 }
 
-impl std::error::Error for ParseEosError {}
+impl core::error::Error for ParseEosError {}
 
 impl FromStr for Eos {
     type Err = ParseEosError;
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        match s.to_ascii_lowercase().as_str() {
-            "ideal" => Ok(Eos::IdealGas),
-            "vdw" => Ok(Eos::VanDerWaals),
-            "rk" => Ok(Eos::RedlichKwong),
-            "srk" => Ok(Eos::SoaveRedlichKwong),
-            "pr" => Ok(Eos::PengRobinson),
-            "ptv" => Ok(Eos::PatelTejaValderrama),
-            _ => Err(ParseEosError(s.to_string()))
+        Eos::all()
+            .iter()
+            .find(|eos| eos.code().eq_ignore_ascii_case(s))
+            .copied()
+            .ok_or_else(|| ParseEosError(s.to_string()))
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::{
+        Berthelot, Clausius, Eos, EquationOfState, IdealGas, LeeKesler, LeeKeslerParams, PatelTejaValderrama, PengRobinson,
+        PengRobinson1976, RedlichKwong, SoaveRedlichKwong, VanDerWaals,
+    };
+    use super::{AbParams, positive_roots};
+    use crate::{Molecule, Pvt, R, SolverOptions, State, compounds};
+    use float_eq::assert_float_eq;
+
+    #[test]
+    fn lee_kesler_matches_nelson_obert_chart() {
+        let simple = LeeKeslerParams {
+            tc: 100.0,
+            pc: 50e5,
+            w: 0.0,
+            options: SolverOptions::default(),
+        };
+        // (Tr, Pr, Z) read on the generalized compressibility chart
+        let chart = [
+            (1.0, 1.0, 0.29),
+            (1.1, 1.0, 0.69),
+            (1.5, 1.0, 0.91),
+            (2.0, 5.0, 0.98),
+        ];
+        for (tr, pr, z) in chart {
+            let lk = LeeKesler::z(&simple, pr * simple.pc, tr * simple.tc).unwrap();
+            assert_float_eq!(lk, z, r2nd <= 0.01);
+        }
+
+        let unconverged = LeeKeslerParams {
+            options: SolverOptions {
+                max_iter: 1,
+                ..SolverOptions::default()
+            },
+            ..simple
+        };
+        assert!(LeeKesler::z(&unconverged, simple.pc, 1.1 * simple.tc).is_none());
+    }
+
+    #[test]
+    fn corresponding_states_chart() {
+        // (Tr, Pr, Z) read on the generalized compressibility chart of Nelson and Obert
+        let chart = [(1.1, 1.0, 0.69), (2.0, 5.0, 0.98)];
+        for (tr, pr, z) in chart {
+            assert_float_eq!(super::z_corresponding_states(tr, pr, 0.0), z, r2nd <= 0.01);
+        }
+        assert_eq!(super::z_corresponding_states(1.5, 0.0, 0.2), 1.0);
+
+        // Z is linear in the acentric factor
+        let z = |w: f64| super::z_corresponding_states(1.3, 2.0, w);
+        assert_float_eq!(z(0.2) - z(0.0), 2.0 * (z(0.1) - z(0.0)), r2nd <= 1e-9);
+    }
+
+    #[test]
+    fn dpdv_matches_finite_difference() {
+        fn check<E: EquationOfState>() {
+            let co2 = compounds::CO2;
+            let t = 320.0;
+            let vm = co2.molar_volume::<E>(50e5, t);
+            let params = co2.eos_params::<E>(t);
+            let h = vm * 1e-6;
+            let fd = (E::pressure(&params, vm + h, t) - E::pressure(&params, vm - h, t)) / (2.0 * h);
+            assert_float_eq!(E::dpdv(&params, vm, t), fd, r2nd <= 1e-6);
+        }
+        check::<IdealGas>();
+        check::<VanDerWaals>();
+        check::<Clausius>();
+        check::<Berthelot>();
+        check::<RedlichKwong>();
+        check::<SoaveRedlichKwong>();
+        check::<PengRobinson>();
+        check::<PatelTejaValderrama>();
+    }
+
+    #[test]
+    fn residual_helmholtz_matches_quadrature() {
+        // compare analytical expressions with the default numerical integration
+        enum Numerical<E> {
+            _E(E),
+        }
+        impl<E: EquationOfState> EquationOfState for Numerical<E> {
+            const NAME: &'static str = E::NAME;
+            type Params = E::Params;
+            fn params(cs: &crate::Pvt, w: f64, t: f64) -> Self::Params {
+                E::params(cs, w, t)
+            }
+            fn pressure(params: &Self::Params, vm: f64, t: f64) -> f64 {
+                E::pressure(params, vm, t)
+            }
+            fn z_polyn(params: &Self::Params, p: f64, t: f64) -> [f64; 4] {
+                E::z_polyn(params, p, t)
+            }
+        }
+
+        fn check<E: EquationOfState>() {
+            let co2 = compounds::CO2;
+            let t = 320.0;
+            let vm = co2.molar_volume::<E>(50e5, t);
+            let params = co2.eos_params::<E>(t);
+            let analytical = E::residual_helmholtz(&params, vm, t);
+            let numerical = Numerical::<E>::residual_helmholtz(&params, vm, t);
+            assert_float_eq!(analytical, numerical, abs <= 1e-9);
+        }
+        check::<IdealGas>();
+        check::<VanDerWaals>();
+        check::<Clausius>();
+        check::<Berthelot>();
+        check::<RedlichKwong>();
+        check::<SoaveRedlichKwong>();
+        check::<PengRobinson>();
+        check::<PatelTejaValderrama>();
+    }
+
+    #[test]
+    fn newton_polish_refines_low_pressure_roots() {
+        use roots::Roots;
+
+        let co2 = compounds::CO2;
+        let t = 300.0;
+        let params = co2.eos_params::<PengRobinson>(t);
+        let polyn = PengRobinson::z_polyn(&params, 1e5, t);
+        let [a3, a2, a1, a0] = polyn;
+        let residual = |x: f64| (((a3 * x + a2) * x + a1) * x + a0).abs();
+
+        let raw = match roots::find_roots_cubic(a3, a2, a1, a0) {
+            Roots::One([r]) => r,
+            Roots::Three([_, _, r]) => r,
+            _ => panic!("unexpected roots"),
+        };
+        let refined = PengRobinson::z(&params, 1e5, t).unwrap();
+        assert_float_eq!(refined, raw, r2nd <= 1e-9);
+        assert!(residual(refined) <= residual(raw));
+        assert!(residual(refined) <= 4.0 * f64::EPSILON);
+    }
+
+    #[test]
+    fn patel_teja_valderrama_uses_c() {
+        // regression: an earlier version of the solver used b in place of c
+        let co2 = compounds::CO2;
+        let (p, t) = (50e5, 320.0);
+        let params = co2.eos_params::<PatelTejaValderrama>(t);
+        let zc = co2.critical_state.z();
+        let c = (0.57765 - 1.78080 * zc) * R * co2.critical_state.t / co2.critical_state.p;
+        assert_float_eq!(params.c, c, r2nd <= 1e-12);
+        assert!((params.c - params.b).abs() > 0.1 * params.b);
+
+        // the Z solution is consistent with the pressure equation
+        let z = co2.z::<PatelTejaValderrama>(p, t);
+        let vm = z * R * t / p;
+        assert_float_eq!(PatelTejaValderrama::pressure(&params, vm, t), p, r2nd <= 1e-9);
+
+        let swapped = super::AbcParams { c: params.b, ..params };
+        let z_swapped = PatelTejaValderrama::z(&swapped, p, t).unwrap();
+        assert!((z - z_swapped).abs() > 1e-3);
+    }
+
+    #[test]
+    fn peng_robinson_params_report() {
+        // CO2 at its critical temperature (α = 1), hand calculation:
+        // a = 0.45724 R²Tc²/Pc = 0.39608 Pa.m6/mol², b = 0.07780 RTc/Pc = 2.6653e-5 m3/mol
+        let co2 = compounds::CO2;
+        let (p, t) = (10e5, co2.critical_state.t);
+        let report = co2.eos_params_report::<PengRobinson>(p, t).unwrap();
+        assert_float_eq!(report.a, 0.39608, r2nd <= 1e-4);
+        assert_float_eq!(report.b, 2.6653e-5, r2nd <= 1e-4);
+        assert_eq!(report.c, None);
+        // A = aP/(RT)² = 0.061956, B = bP/RT = 0.010541
+        assert_float_eq!(report.a_dimensionless, 0.061956, r2nd <= 1e-4);
+        assert_float_eq!(report.b_dimensionless, 0.010541, r2nd <= 1e-4);
+
+        let report = co2.eos_params_report::<PatelTejaValderrama>(p, t).unwrap();
+        assert!(report.c.is_some());
+        assert_eq!(co2.eos_params_report::<IdealGas>(p, t), None);
+    }
+
+    /// Check that every positive root of [`EquationOfState::z_polyn`] for `molecule`
+    /// gives back the pressure `p` with [`EquationOfState::pressure`]
+    fn verify_consistency<E: EquationOfState>(molecule: &Molecule, p: f64, t: f64) -> Result<(), String> {
+        let params = molecule.eos_params::<E>(t);
+        let roots = positive_roots(E::z_polyn(&params, p, t));
+        if roots.is_empty() {
+            return Err(format!("{}: no root at {p} Pa, {t} K", E::NAME));
+        }
+        for z in roots {
+            let pz = E::pressure(&params, z * R * t / p, t);
+            if (pz - p).abs() > 1e-8 * p {
+                return Err(format!("{}: Z = {z} gives {pz} Pa instead of {p} Pa at {t} K", E::NAME));
+            }
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn z_polyn_consistent_with_pressure() {
+        fn verify<E: EquationOfState>() {
+            // gas, supercritical, dense and two-phase conditions
+            for (m, p, t) in [
+                (compounds::N2, 1e5, 300.0),
+                (compounds::N2, 500e5, 200.0),
+                (compounds::CO2, 40e5, 280.0),
+                (compounds::CO2, 100e5, 320.0),
+                (compounds::H2O, 1e5, 350.0),
+                (compounds::CH4, 20e5, 150.0),
+            ] {
+                verify_consistency::<E>(&m, p, t).unwrap();
+            }
+        }
+        verify::<VanDerWaals>();
+        verify::<Clausius>();
+        verify::<Berthelot>();
+        verify::<RedlichKwong>();
+        verify::<SoaveRedlichKwong>();
+        verify::<PengRobinson>();
+        verify::<PengRobinson1976>();
+        verify::<PatelTejaValderrama>();
+
+        // Peng-Robinson with the two last coefficients swapped
+        enum BrokenPengRobinson {}
+        impl EquationOfState for BrokenPengRobinson {
+            const NAME: &'static str = "Broken Peng-Robinson";
+            type Params = AbParams;
+            fn params(cs: &Pvt, w: f64, t: f64) -> AbParams {
+                PengRobinson::params(cs, w, t)
+            }
+            fn pressure(params: &AbParams, vm: f64, t: f64) -> f64 {
+                PengRobinson::pressure(params, vm, t)
+            }
+            fn z_polyn(params: &AbParams, p: f64, t: f64) -> [f64; 4] {
+                let [a3, a2, a1, a0] = PengRobinson::z_polyn(params, p, t);
+                [a3, a2, a0, a1]
+            }
+        }
+        assert!(verify_consistency::<BrokenPengRobinson>(&compounds::CO2, 100e5, 320.0).is_err());
+    }
+
+    #[test]
+    fn z_polyn_matches_textbook_forms() {
+        // the refactored coefficients must be bit-identical to the textbook expressions
+        let (p, t) = (3e6, 320.0);
+        let ab = |a: f64, b: f64| (a * p / (R * R * t * t), b * p / (R * t));
+
+        let co2 = compounds::CO2;
+        let params = co2.eos_params::<PengRobinson>(t);
+        let (a, b) = ab(params.a, params.b);
+        assert_eq!(
+            PengRobinson::z_polyn(&params, p, t),
+            [1.0, b - 1.0, -3.0 * b * b - 2.0 * b + a, b * b * b + b * b - a * b]
+        );
+
+        let params = co2.eos_params::<VanDerWaals>(t);
+        let (a, b) = ab(params.a, params.b);
+        assert_eq!(VanDerWaals::z_polyn(&params, p, t), [1.0, -b - 1.0, a, -a * b]);
+
+        let params = co2.eos_params::<SoaveRedlichKwong>(t);
+        let (a, b) = ab(params.a, params.b);
+        assert_eq!(SoaveRedlichKwong::z_polyn(&params, p, t), [1.0, -1.0, a - b * b - b, -a * b]);
+
+        let params = compounds::H2O.eos_params::<PatelTejaValderrama>(t);
+        let (a, b) = ab(params.a, params.b);
+        let c = params.c * p / (R * t);
+        assert_eq!(
+            PatelTejaValderrama::z_polyn(&params, p, t),
+            [1.0, c - 1.0, -2.0 * b * c - b * b - b - c + a, b * b * c + b * c - a * b]
+        );
+    }
+
+    #[test]
+    fn custom_cubic_matches_van_der_waals() {
+        use super::CustomCubic;
+        use crate::StateEos;
+
+        fn params(cs: &Pvt, _w: f64, _t: f64) -> AbParams {
+            AbParams {
+                a: 27.0 * R * R * cs.t * cs.t / (64.0 * cs.p),
+                b: R * cs.t / (8.0 * cs.p),
+            }
+        }
+        fn pressure(params: &AbParams, vm: f64, t: f64) -> f64 {
+            R * t / (vm - params.b) - params.a / (vm * vm)
         }
+        fn z_polyn(params: &AbParams, p: f64, t: f64) -> [f64; 4] {
+            let a = params.a * p / (R * R * t * t);
+            let b = params.b * p / (R * t);
+            [1.0, -(b + 1.0), a, -a * b]
+        }
+        let vdw = CustomCubic {
+            name: "Custom Van der Waals",
+            params,
+            pressure,
+            z_polyn,
+        };
+
+        let n2 = compounds::N2;
+        let air = compounds::dry_air();
+        for (p, t) in [(1e5, 300.0), (100e5, 200.0), (500e5, 400.0)] {
+            let z = n2.z_dyn(&vdw, p, t);
+            assert_float_eq!(z, n2.z::<VanDerWaals>(p, t), r2nd <= 1e-12);
+            let z = air.z_dyn(&vdw, p, t);
+            assert_float_eq!(z, air.z::<VanDerWaals>(p, t), r2nd <= 1e-12);
+
+            let vm = air.molar_volume::<VanDerWaals>(p, t);
+            let p_dyn = air.pressure_dyn(&vdw, vm, t);
+            assert_float_eq!(p_dyn, air.pressure::<VanDerWaals>(vm, t), r2nd <= 1e-12);
+        }
+    }
+
+    #[test]
+    fn clausius_and_berthelot_approach_ideal_gas() {
+        fn check<E: EquationOfState>() {
+            for m in [compounds::N2, compounds::CO2, compounds::CH4] {
+                for t in [250.0, 400.0] {
+                    let z = m.z::<E>(1e3, t);
+                    assert_ne!(z, 1.0);
+                    assert_float_eq!(z, 1.0, abs <= 1e-4);
+                    // Z - 1 ≈ BP/RT at low pressure
+                    let b = m.second_virial::<E>(t);
+                    assert_float_eq!(z - 1.0, b * 1e3 / (R * t), r2nd <= 1e-3);
+                }
+            }
+        }
+        check::<Clausius>();
+        check::<Berthelot>();
+
+        // Berthelot is Van der Waals with the attraction scaled by Tc/T
+        let n2 = compounds::N2;
+        let tc = n2.critical_state.t;
+        assert_eq!(n2.z::<Berthelot>(50e5, tc), n2.z::<VanDerWaals>(50e5, tc));
+        assert!(n2.z::<Berthelot>(50e5, 300.0) > n2.z::<VanDerWaals>(50e5, 300.0));
+    }
+
+    #[test]
+    fn names() {
+        assert_eq!(PengRobinson::NAME, "Peng-Robinson");
+        assert_eq!(SoaveRedlichKwong::NAME, "Soave-Redlich-Kwong");
+        assert_eq!(LeeKesler::NAME, "Lee-Kesler");
+    }
+
+    #[test]
+    fn all_eos_round_trip() {
+        for eos in Eos::all() {
+            assert_eq!(eos.code().to_uppercase().parse::<Eos>().unwrap(), *eos);
+        }
+        assert_eq!(Eos::PengRobinson.name(), "Peng-Robinson");
+        assert!("xyz".parse::<Eos>().is_err());
+    }
+
+    #[test]
+    fn recommended() {
+        use super::recommended_eos;
+
+        assert_eq!(recommended_eos(&compounds::H2O.into()), Eos::PatelTejaValderrama);
+        assert_eq!(recommended_eos(&compounds::C2H5OH.into()), Eos::PatelTejaValderrama);
+        assert_eq!(recommended_eos(&compounds::AR.into()), Eos::PengRobinson);
+        assert_eq!(recommended_eos(&compounds::natural_gas().into()), Eos::PengRobinson);
+    }
+
+    #[test]
+    fn peng_robinson_1976() {
+        let etoh = compounds::C2H5OH;
+        let (p, t) = (1e5, 500.0);
+        let z78 = etoh.z::<PengRobinson>(p, t);
+        let z76 = etoh.z::<PengRobinson1976>(p, t);
+        assert_ne!(z76, z78);
+        assert_float_eq!(z76, z78, r2nd <= 0.01);
+        assert!(matches!("PR76".parse::<Eos>(), Ok(Eos::PengRobinson1976)));
+    }
+
+    #[test]
+    fn degenerate_cubic() {
+        // a3 close to zero: Z² - 3Z + 2 = 0
+        let roots = super::positive_roots([1e-20, 1.0, -3.0, 2.0]);
+        assert_eq!(roots.len(), 2);
+        assert_float_eq!(roots[0], 1.0, abs <= 1e-12);
+        assert_float_eq!(roots[1], 2.0, abs <= 1e-12);
+
+        // linear
+        assert_eq!(super::positive_roots([0.0, 0.0, 2.0, -1.0]), vec![0.5]);
+        // no root
+        assert!(super::positive_roots([0.0, 0.0, 0.0, 1.0]).is_empty());
+    }
+
+    #[test]
+    fn ideal_gas_z_is_one() {
+        let air = compounds::dry_air();
+        for t in [50.0, 273.15, 1000.0, 5000.0] {
+            for p in [1.0, 1e5, 100e5, 1e9] {
+                assert_eq!(air.z::<IdealGas>(p, t), 1.0);
+                assert_eq!(super::positive_roots(IdealGas::z_polyn(&(), p, t)), vec![1.0]);
+            }
+        }
+    }
+
+    #[test]
+    fn lee_kesler_pressure_inverts_z() {
+        let co2 = compounds::CO2;
+        let p = 50e5;
+        let t = 350.0;
+        let vm = co2.molar_volume::<LeeKesler>(p, t);
+        assert_float_eq!(co2.pressure::<LeeKesler>(vm, t), p, r2nd <= 1e-6);
+        assert!(vm < R * t / p);
     }
 }
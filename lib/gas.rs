@@ -1,5 +1,12 @@
-use crate::{Pvt, compounds};
-use std::{borrow::Borrow, cmp::Reverse, fmt, num::ParseFloatError, str::FromStr};
+use crate::{
+    Pvt, R, State,
+    compounds::{self, Compounds},
+    eos::{self, AlphaFunction, EquationOfState},
+    solver::{self, SolverError, SolverOptions},
+};
+#[cfg(not(feature = "std"))]
+use alloc::{format, string::{String, ToString}, vec::Vec};
+use core::{borrow::Borrow, cmp::Reverse, fmt, num::ParseFloatError, ops::Range, str::FromStr};
 
 /// A gas molecule, represented by its physical properties.
 #[derive(Debug, Clone, Copy, PartialEq)]
@@ -8,12 +15,86 @@ pub struct Molecule {
     pub m: f64,
     /// The critical state of this molecule
     pub critical_state: Pvt,
-    /// The acentric factor
-    pub w: f64,
+    /// The acentric factor, if known.
+    /// See [`Molecule::acentric_factor`].
+    pub w: Option<f64>,
+    /// The dipole moment in debye, zero for non-polar molecules
+    pub dipole: f64,
+    /// The quantum correction of the critical constants, for very light fluids
+    pub quantum: Option<QuantumCorrection>,
+    /// The alpha function used by the Soave-Redlich-Kwong and Peng-Robinson equations
+    pub alpha: AlphaFunction,
+    /// The range where the data of this molecule is known to be valid, if any
+    pub validity: Option<ValidityRange>,
+    /// The isobaric heat capacity of the ideal gas, if known
+    pub cp: Option<IdealCp>,
+}
+
+/// The isobaric heat capacity of the ideal gas, as a polynomial of the temperature:
+/// Cp/R = a0 + a1·T + a2·T² + a3·T³ + a4·T⁴
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+pub struct IdealCp {
+    /// The polynomial coefficients, from a0 to a4, with T in K
+    pub a: [f64; 5],
+}
+
+impl IdealCp {
+    /// The heat capacity of monatomic gases, Cp = 5/2·R
+    pub const MONATOMIC: IdealCp = IdealCp {
+        a: [2.5, 0.0, 0.0, 0.0, 0.0],
+    };
+
+    /// The molar heat capacity at the temperature `t`, in J/mol.K
+    pub fn cp(&self, t: f64) -> f64 {
+        R * self.a.iter().rev().fold(0.0, |cp, a| cp * t + a)
+    }
+}
+
+/// The range of temperature and pressure where the data of a molecule is valid
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+pub struct ValidityRange {
+    /// The minimum temperature, in K
+    pub t_min: f64,
+    /// The maximum temperature, in K
+    pub t_max: f64,
+    /// The maximum pressure, in Pa
+    pub p_max: f64,
+}
+
+/// Whether a state is in the validity range of a molecule
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Validity {
+    /// The state is within the validity range
+    InRange,
+    /// The state is outside of the validity range
+    OutOfRange,
+    /// The molecule has no validity range data
+    Unknown,
+}
+
+/// Quantum correction of the critical constants of very light fluids such as hydrogen,
+/// helium and neon, after Chueh and Prausnitz (1967).
+///
+/// At cryogenic temperatures the classical equations of state mispredict the behavior of
+/// these fluids. The correction replaces the critical constants by temperature dependent
+/// effective values:
+///  * Tc = Tc0 / (1 + 21.8 / (M·T))
+///  * Pc = Pc0 / (1 + 44.2 / (M·T))
+///  * Vc = Vc0 / (1 - 9.91 / (M·T))
+///
+/// where M is the molar mass in g/mol. The acentric factor is taken as zero.
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+pub struct QuantumCorrection {
+    /// The classical critical temperature, in K
+    pub tc0: f64,
+    /// The classical critical pressure, in Pa
+    pub pc0: f64,
+    /// The classical critical volume, in m3/mol
+    pub vc0: f64,
 }
 
 impl PartialOrd for Molecule {
-    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+    fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
         self.m
             .partial_cmp(&other.m)
             .or_else(|| self.critical_state.partial_cmp(&other.critical_state))
@@ -21,7 +102,220 @@ impl PartialOrd for Molecule {
     }
 }
 
-/// A mixture of several gases
+impl Molecule {
+    /// The provenance of the critical constants, for the built-in compounds only.
+    ///
+    /// This is informative and doesn't affect the calculations.
+    pub fn meta(&self) -> Option<compounds::CompoundMeta> {
+        compounds::meta(self)
+    }
+
+    /// A molecule from its critical temperature `tc` (in K), pressure `pc` (in Pa),
+    /// molar volume `vc` (in m3/mol), acentric factor `w` and molar mass `m` (in kg/mol).
+    ///
+    /// The molecule is non-polar, has the standard alpha function and no other data.
+    pub const fn from_critical(tc: f64, pc: f64, vc: f64, w: f64, m: f64) -> Molecule {
+        Molecule {
+            m,
+            critical_state: Pvt { p: pc, v: vc, t: tc },
+            w: Some(w),
+            dipole: 0.0,
+            quantum: None,
+            alpha: AlphaFunction::Standard,
+            validity: None,
+            cp: None,
+        }
+    }
+
+    /// The molar mass of the molecule, in kg/mol
+    pub fn molar_mass(&self) -> f64 {
+        self.m
+    }
+
+    /// This molecule with the given quantum correction
+    pub fn with_quantum_correction(self, quantum: QuantumCorrection) -> Molecule {
+        Molecule {
+            quantum: Some(quantum),
+            ..self
+        }
+    }
+
+    /// This molecule with the given alpha function
+    pub fn with_alpha(self, alpha: AlphaFunction) -> Molecule {
+        Molecule { alpha, ..self }
+    }
+
+    /// Whether the pressure `p` (in Pa) and temperature `t` (in K)
+    /// are in the validity range of this molecule
+    pub fn validity(&self, p: f64, t: f64) -> Validity {
+        match self.validity {
+            None => Validity::Unknown,
+            Some(r) if t >= r.t_min && t <= r.t_max && p <= r.p_max => Validity::InRange,
+            Some(_) => Validity::OutOfRange,
+        }
+    }
+
+    /// The critical state and acentric factor to be used in the equations of state at temperature `t`.
+    ///
+    /// This is the critical state of the molecule, unless a quantum correction applies.
+    pub fn effective_critical_state(&self, t: f64) -> (Pvt, f64) {
+        match self.quantum {
+            None => (self.critical_state, self.acentric_factor()),
+            Some(QuantumCorrection { tc0, pc0, vc0 }) => {
+                let mt = self.m * 1000.0 * t;
+                let cs = Pvt {
+                    p: pc0 / (1.0 + 44.2 / mt),
+                    v: vc0 / (1.0 - 9.91 / mt),
+                    t: tc0 / (1.0 + 21.8 / mt),
+                };
+                (cs, 0.0)
+            }
+        }
+    }
+
+    /// The critical compression factor Zc = PcVc/RTc
+    pub fn critical_compressibility(&self) -> f64 {
+        self.critical_state.z()
+    }
+
+    /// The acentric factor used by the equations of state.
+    ///
+    /// This is [`Molecule::w`] if it is known,
+    /// or [`Molecule::estimated_acentric_factor`] otherwise.
+    pub fn acentric_factor(&self) -> f64 {
+        self.w.unwrap_or_else(|| self.estimated_acentric_factor())
+    }
+
+    /// The acentric factor estimated from the critical compression factor,
+    /// with the correlation of Lee and Kesler (1975): Zc = 0.2905 - 0.085ω
+    ///
+    /// This is a rough estimate, only suitable for non-polar fluids
+    /// whose acentric factor is unknown.
+    pub fn estimated_acentric_factor(&self) -> f64 {
+        (0.2905 - self.critical_compressibility()) / 0.085
+    }
+
+    /// Whether the molar mass, critical state and acentric factor of both molecules
+    /// are equal within the relative tolerance `rtol`.
+    ///
+    /// An unknown acentric factor only equals another unknown one.
+    pub fn approx_eq(&self, other: &Molecule, rtol: f64) -> bool {
+        let (cl, cr) = (&self.critical_state, &other.critical_state);
+        let w_eq = match (self.w, other.w) {
+            (Some(wl), Some(wr)) => approx_eq(wl, wr, rtol),
+            (wl, wr) => wl.is_none() && wr.is_none(),
+        };
+        approx_eq(self.m, other.m, rtol)
+            && approx_eq(cl.p, cr.p, rtol)
+            && approx_eq(cl.v, cr.v, rtol)
+            && approx_eq(cl.t, cr.t, rtol)
+            && w_eq
+    }
+
+    /// The reduced temperature Tr = T/Tc
+    pub fn reduced_temperature(&self, t: f64) -> f64 {
+        t / self.critical_state.t
+    }
+
+    /// The reduced pressure Pr = P/Pc
+    pub fn reduced_pressure(&self, p: f64) -> f64 {
+        p / self.critical_state.p
+    }
+
+    /// The Boyle temperature in K, that is the temperature at which the second virial
+    /// coefficient B(T) implied by the equation of state crosses zero.
+    ///
+    /// The crossing is searched between 0.5·Tc and 50·Tc.
+    /// Returns `None` if no crossing is found in this range.
+    pub fn boyle_temperature<E: EquationOfState>(&self) -> Option<f64> {
+        self.boyle_temperature_with_options::<E>(&SolverOptions::default()).ok()
+    }
+
+    /// The Boyle temperature in K like [`Molecule::boyle_temperature`],
+    /// with the convergence control of `options`
+    pub fn boyle_temperature_with_options<E: EquationOfState>(
+        &self,
+        options: &SolverOptions,
+    ) -> Result<f64, SolverError> {
+        // B is reduced by RTc/Pc, as the solver tolerance also applies to it
+        let cs = self.critical_state;
+        let b = |t: f64| E::second_virial(&self.eos_params::<E>(t), t) * cs.p / (R * cs.t);
+
+        let tc = cs.t;
+        let mut lo = 0.5 * tc;
+        let mut b_lo = b(lo);
+        loop {
+            let hi = lo * 1.1;
+            if hi > 50.0 * tc {
+                return Err(SolverError::NoSolution);
+            }
+            let b_hi = b(hi);
+            if b_lo < 0.0 && b_hi >= 0.0 {
+                return solver::find_root_brent(lo, hi, b, hi, options);
+            }
+            lo = hi;
+            b_lo = b_hi;
+        }
+    }
+
+    /// A quick estimate of the compression factor at the pressure `p` (in Pa) and temperature `t` (in K),
+    /// from the reduced properties and [`eos::z_corresponding_states`].
+    pub fn z_corresponding_states(&self, p: f64, t: f64) -> f64 {
+        let (cs, w) = self.effective_critical_state(t);
+        eos::z_corresponding_states(t / cs.t, p / cs.p, w)
+    }
+
+    /// The sensitivity ∂Z/∂ω of the compression factor to the acentric factor,
+    /// at the pressure `p` (in Pa) and temperature `t` (in K).
+    ///
+    /// This is computed with a central finite difference on ω in the equation of state parameters,
+    /// and tells how much Z depends on an uncertain acentric factor.
+    /// It is zero with a quantum correction, where the acentric factor of the molecule is not used.
+    ///
+    /// # Panics
+    /// This function will panic if no positive real root can be found, like [`State::z`].
+    pub fn dz_dw<E: EquationOfState>(&self, p: f64, t: f64) -> f64 {
+        const H: f64 = 1e-4;
+
+        if self.quantum.is_some() {
+            return 0.0;
+        }
+
+        let (cs, w) = self.effective_critical_state(t);
+        let z = |w: f64| {
+            let params = E::params_with_alpha(&cs, w, &self.alpha, t);
+            E::z(&params, p, t).expect("Should have a found a positive real root")
+        };
+        (z(w + H) - z(w - H)) / (2.0 * H)
+    }
+}
+
+/// Displays the symbol of the molecule if it is a built-in compound,
+/// or a summary of its properties otherwise.
+impl fmt::Display for Molecule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match compounds::symbol(self) {
+            Some(symbol) => f.write_str(symbol),
+            None => {
+                write!(
+                    f,
+                    "{{M={} kg/mol, Tc={} K, Pc={} Pa, Vc={} m3/mol",
+                    self.m, self.critical_state.t, self.critical_state.p, self.critical_state.v,
+                )?;
+                match self.w {
+                    Some(w) => write!(f, ", w={w}}}"),
+                    None => f.write_str(", w unknown}"),
+                }
+            }
+        }
+    }
+}
+
+/// A mixture of several gases.
+///
+/// A mixture only holds molecules with their molar fraction:
+/// mixtures given as components are flattened when the mixture is built,
+/// so mixtures are never nested, whatever the nesting of the input.
 #[derive(Debug, Clone, PartialEq)]
 pub struct Mixture {
     pub(crate) comps: Vec<(f64, Molecule)>,
@@ -30,36 +324,160 @@ pub struct Mixture {
 /// A mixture error
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum MixtureError {
-    MixtureNotWhole,
+    /// The sum of explicit fractions exceeds 100%,
+    /// or reaches 100% while a remainder component is also given
+    Overfilled(f64),
+    /// The sum of fractions is below 100% and no remainder component is given
+    Underfilled(f64),
     InvalidFraction(f64),
+    MixedBasis,
+    /// More than one remainder component is given to a [`MixtureBuilder`]
+    MultipleRemainders,
 }
 
 impl fmt::Display for MixtureError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
-            MixtureError::MixtureNotWhole => write!(f, "The sum of fractions does not equal to 100%"),
+            MixtureError::Overfilled(fill) => write!(
+                f,
+                "The sum of fractions ({}%) leaves nothing for the remainder of the mixture",
+                fill * 100.0
+            ),
+            MixtureError::Underfilled(fill) => write!(
+                f,
+                "The sum of fractions ({}%) is below 100% and no remainder is given",
+                fill * 100.0
+            ),
             MixtureError::InvalidFraction(fraction) => write!(f, "{:.1}% isn't a valid molar fraction", fraction),
+            MixtureError::MixedBasis => write!(f, "Molar and mass fractions can't be mixed in a mixture"),
+            MixtureError::MultipleRemainders => write!(f, "A mixture can't have more than one remainder"),
         }
         
     }
 }
 
-impl std::error::Error for MixtureError {}
+impl core::error::Error for MixtureError {}
+
+/// Displays the mixture with the grammar parsed by `Gas::from_str`,
+/// e.g. "78.08%N2+20.95%O2+0.93%Ar+CO2".
+/// The last component is written as the remainder of the mixture.
+impl fmt::Display for Mixture {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let last = self.comps.len().saturating_sub(1);
+        for (idx, (frac, m)) in self.comps.iter().enumerate() {
+            if idx > 0 {
+                f.write_str("+")?;
+            }
+            if idx < last {
+                let pct = frac * 100.0;
+                match f.precision() {
+                    Some(prec) => write!(f, "{pct:.prec$}%")?,
+                    None => {
+                        let pct = format!("{pct:.10}");
+                        let pct = pct.trim_end_matches('0').trim_end_matches('.');
+                        write!(f, "{pct}%")?
+                    }
+                }
+            }
+            write!(f, "{m}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Whether `f` is a valid explicit fraction, that is in ]0, 1[ (NaN is rejected)
+fn valid_fraction(f: f64) -> bool {
+    f > 0.0 && f < 1.0
+}
+
+/// Check that explicit fractions summing to `fill` make a whole mixture with `num_voids` remainders
+fn check_fill(fill: f64, num_voids: usize) -> Result<(), MixtureError> {
+    if fill > 1.0 || (fill >= 1.0 && num_voids > 0) {
+        Err(MixtureError::Overfilled(fill))
+    } else if fill != 1.0 && num_voids == 0 {
+        Err(MixtureError::Underfilled(fill))
+    } else {
+        Ok(())
+    }
+}
 
 /// A component to build a mixture
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub enum Comp {
+    /// A gas with its molar fraction
     Factor(f64, Gas),
+    /// A gas with its mass fraction
+    MassFactor(f64, Gas),
+    /// A gas that fills the remainder of the mixture
     Remainder(Gas),
 }
 
+/// Build a [`Mixture`] by adding its components one at a time
+#[derive(Debug, Clone, Default)]
+pub struct MixtureBuilder {
+    comps: Vec<Comp>,
+}
+
+impl MixtureBuilder {
+    /// An empty builder
+    pub fn new() -> Self {
+        MixtureBuilder::default()
+    }
+
+    /// Add `gas` with the molar `fraction`
+    pub fn add<G: Into<Gas>>(mut self, fraction: f64, gas: G) -> Self {
+        self.comps.push(Comp::Factor(fraction, gas.into()));
+        self
+    }
+
+    /// Add `gas` as the remainder of the mixture
+    pub fn add_remainder<G: Into<Gas>>(mut self, gas: G) -> Self {
+        self.comps.push(Comp::Remainder(gas.into()));
+        self
+    }
+
+    /// Build the mixture with [`Mixture::new`].
+    ///
+    /// Fails if more than one remainder was added.
+    pub fn build(self) -> Result<Mixture, MixtureError> {
+        let num_voids = self
+            .comps
+            .iter()
+            .filter(|c| matches!(c, Comp::Remainder(_)))
+            .count();
+        if num_voids > 1 {
+            return Err(MixtureError::MultipleRemainders);
+        }
+        Mixture::new(self.comps)
+    }
+}
+
 impl Mixture {
+    /// Build a mixture from its components.
+    ///
+    /// If the components are given with mass fractions, they are converted to molar
+    /// fractions with [`Mixture::from_mass_fractions`].
+    ///
+    /// A component that is itself a mixture is flattened: each of its molecules is added
+    /// with its fraction in the component, scaled by the fraction of the component.
+    /// As a mixture is always flat, this is a single level and doesn't recurse,
+    /// e.g. 50% of (50% O2 + N2) with 50% of CO2 gives 25% O2, 25% N2 and 50% CO2.
+    /// Identical molecules are then merged.
     pub fn new<I>(comps: I) -> Result<Mixture, MixtureError>
     where
         I: IntoIterator,
         I::Item: Borrow<Comp>,
     {
-        let mut tmp: Vec<(bool, f64, Molecule)> = Vec::new(); // first tuple field means remainder
+        let comps: Vec<I::Item> = comps.into_iter().collect();
+        if comps
+            .iter()
+            .any(|c| matches!(c.borrow(), Comp::MassFactor(..)))
+        {
+            return Mixture::from_mass_fractions(comps.iter().map(|c| c.borrow()));
+        }
+
+        // first tuple field means remainder, in which case the fraction is relative to the remainder
+        let mut tmp: Vec<(bool, f64, Molecule)> = Vec::new();
         let mut fill = 0f64;
         let mut num_voids = 0;
 
@@ -67,60 +485,53 @@ impl Mixture {
             let c = c.borrow();
 
             let (f, g) = match c {
-                Comp::Factor(f, g) => (*f, g),
-                Comp::Remainder(g) => (f64::NAN, g),
+                Comp::Factor(f, g) => (Some(*f), g),
+                Comp::Remainder(g) => (None, g),
+                Comp::MassFactor(..) => unreachable!(),
             };
-            if f.is_nan() {
-                num_voids += 1;
-            } else {
-                if f <= 0f64 || f >= 1f64 {
-                    return Err(MixtureError::InvalidFraction(f));
-                }
-                fill += f;
+            match f {
+                None => num_voids += 1,
+                Some(f) if !valid_fraction(f) => return Err(MixtureError::InvalidFraction(f)),
+                Some(f) => fill += f,
             }
+            let remainder = f.is_none();
+            let f = f.unwrap_or(1.0);
             match g {
-                Gas::Molecule(m) => {
-                    tmp.push((f.is_nan(), f, *m));
-                }
+                Gas::Molecule(m) => tmp.push((remainder, f, *m)),
                 Gas::Mixture(Mixture { comps }) => {
                     for c in comps {
-                        if f.is_nan() {
-                            tmp.push((true, c.0, c.1));
-                        } else {
-                            tmp.push((false, f * c.0, c.1));
-                        }
+                        tmp.push((remainder, f * c.0, c.1));
                     }
                 }
             }
         }
 
-        if fill > 1.0 {
-            return Err(MixtureError::MixtureNotWhole);
-        }
-        if fill != 1.0 && num_voids == 0 {
-            return Err(MixtureError::MixtureNotWhole);
-        }
+        check_fill(fill, num_voids)?;
 
         if num_voids > 0 {
             let void_attrib = (1.0 - fill) / num_voids as f64;
             for c in &mut tmp {
                 if c.0 {
-                    if c.1.is_nan() {
-                        c.1 = void_attrib;
-                    } else {
-                        c.1 *= void_attrib;
-                    }
+                    c.1 *= void_attrib;
                 }
             }
         }
 
-        let mut comps: Vec<(f64, Molecule)> = tmp.into_iter().map(|(_, f, m)| (f, m)).collect();
-
-        // Following sort and merge make the components always the same for a given mixture.
+        // Following merge and sort make the components always the same for a given mixture.
         // e.g. mixing air with O2 will result with a single O2 component instead of 2,
         // and components will always be in the same order.
         // This makes mixtures trivially comparable
 
+        // merge gases that have identical properties, before sorting as the fractions
+        // of identical gases may differ and not be adjacent once sorted
+        let mut comps: Vec<(f64, Molecule)> = Vec::with_capacity(tmp.len());
+        for (_, f, m) in tmp {
+            match comps.iter_mut().find(|(_, cm)| *cm == m) {
+                Some(c) => c.0 += f,
+                None => comps.push((f, m)),
+            }
+        }
+
         // sort with decreasing order of ratio, followed by decreasing order of molar mass
         // followed by decreasing order of critical parameters
         comps.sort_by(|(fa, ma), (fb, mb)| {
@@ -129,26 +540,304 @@ impl Mixture {
                 .unwrap()
         });
 
-        // merge gases that have identical properties
-        let mut i1 = 0;
-        let mut i2 = 1;
-        while i2 < comps.len() {
-            if comps[i1].1 == comps[i2].1 {
-                comps[i1].0 += comps[i2].0;
-                comps.remove(i2);
+        debug_assert!(comps.iter().map(|(f, _)| *f).sum::<f64>() > 0.9999999);
+        debug_assert!(comps.iter().map(|(f, _)| *f).sum::<f64>() < 1.0000001);
+
+        Ok(Mixture { comps })
+    }
+
+    /// Build a mixture from components given with mass fractions.
+    ///
+    /// Components must be either [`Comp::MassFactor`] or [`Comp::Remainder`],
+    /// in which case the remainder is attributed in mass.
+    /// The mass fractions are converted to molar fractions using the molar mass
+    /// of each component before the mixture is built.
+    pub fn from_mass_fractions<I>(comps: I) -> Result<Mixture, MixtureError>
+    where
+        I: IntoIterator,
+        I::Item: Borrow<Comp>,
+    {
+        let comps: Vec<I::Item> = comps.into_iter().collect();
+
+        let mut fill = 0f64;
+        let mut num_voids = 0;
+        for c in comps.iter() {
+            match c.borrow() {
+                Comp::MassFactor(f, _) => {
+                    if !valid_fraction(*f) {
+                        return Err(MixtureError::InvalidFraction(*f));
+                    }
+                    fill += f;
+                }
+                Comp::Remainder(_) => num_voids += 1,
+                Comp::Factor(..) => return Err(MixtureError::MixedBasis),
+            }
+        }
+
+        check_fill(fill, num_voids)?;
+        let void_attrib = if num_voids > 0 {
+            (1.0 - fill) / num_voids as f64
+        } else {
+            0.0
+        };
+
+        // amount of mols per unit of mass of mixture
+        let mols: Vec<(f64, &Gas)> = comps
+            .iter()
+            .map(|c| match c.borrow() {
+                Comp::MassFactor(f, g) => (f / g.molar_mass(), g),
+                Comp::Remainder(g) => (void_attrib / g.molar_mass(), g),
+                Comp::Factor(..) => unreachable!(),
+            })
+            .collect();
+        let total: f64 = mols.iter().map(|(n, _)| n).sum();
+
+        // the last component is given as remainder to absorb rounding errors
+        let last = mols.len() - 1;
+        let mole_comps: Vec<Comp> = mols
+            .into_iter()
+            .enumerate()
+            .map(|(idx, (n, g))| {
+                if idx == last {
+                    Comp::Remainder(g.clone())
+                } else {
+                    Comp::Factor(n / total, g.clone())
+                }
+            })
+            .collect();
+
+        Mixture::new(mole_comps)
+    }
+
+    /// Build a mixture from molar fractions that may not sum to one,
+    /// such as rounded analyzer percentages.
+    ///
+    /// The fractions are rescaled by their sum, so the given values are only relative amounts:
+    /// e.g. 78% N2 and 21.7% O2 (summing to 99.7%) gives 78.23% N2 and 21.77% O2.
+    /// Fractions that are not positive are rejected with [`MixtureError::InvalidFraction`].
+    /// Use [`Mixture::new`] for a strict validation of the sum.
+    pub fn from_unnormalized<I>(comps: I) -> Result<Mixture, MixtureError>
+    where
+        I: IntoIterator<Item = (f64, Gas)>,
+    {
+        let comps: Vec<(f64, Gas)> = comps.into_iter().collect();
+        if let Some((f, _)) = comps.iter().find(|(f, _)| f.is_nan() || *f <= 0.0) {
+            return Err(MixtureError::InvalidFraction(*f));
+        }
+        let total: f64 = comps.iter().map(|(f, _)| f).sum();
+        if comps.is_empty() || !total.is_finite() {
+            return Err(MixtureError::Underfilled(total));
+        }
+
+        // the last component is given as remainder to absorb rounding errors
+        let last = comps.len() - 1;
+        let comps: Vec<Comp> = comps
+            .into_iter()
+            .enumerate()
+            .map(|(idx, (f, g))| {
+                if idx == last {
+                    Comp::Remainder(g)
+                } else {
+                    Comp::Factor(f / total, g)
+                }
+            })
+            .collect();
+
+        Mixture::new(comps)
+    }
+
+    /// Dilute the mixture with the molar `fraction` of the `inert` gas.
+    ///
+    /// The components of the mixture are scaled by `1 - fraction`,
+    /// and merged with the inert if it is already part of the mixture.
+    /// A `fraction` out of `]0, 1[` is rejected with [`MixtureError::InvalidFraction`].
+    pub fn dilute<G: Into<Gas>>(&self, inert: G, fraction: f64) -> Result<Mixture, MixtureError> {
+        if fraction.is_nan() || fraction <= 0.0 || fraction >= 1.0 {
+            return Err(MixtureError::InvalidFraction(fraction));
+        }
+        MixtureBuilder::new()
+            .add(fraction, inert)
+            .add_remainder(self.clone())
+            .build()
+    }
+
+    /// The mixture without the molecule `m`, the other components being renormalized.
+    ///
+    /// The mixture is unchanged if it doesn't contain `m`,
+    /// and removing the only molecule fails with [`MixtureError::Underfilled`].
+    pub fn without(&self, m: &Molecule) -> Result<Mixture, MixtureError> {
+        let comps = self.comps.iter().filter(|(_, cm)| cm != m);
+        Mixture::from_unnormalized(comps.map(|(f, cm)| (*f, Gas::Molecule(*cm))))
+    }
+
+    /// The mixture where the molecule `old` is replaced by the gas `new` with the same molar fraction.
+    ///
+    /// The mixture is unchanged if it doesn't contain `old`.
+    /// If `new` is a mixture, it is flattened, and merged with identical molecules of the mixture.
+    pub fn substitute<G: Into<Gas>>(&self, old: &Molecule, new: G) -> Result<Mixture, MixtureError> {
+        let new = new.into();
+        let comps = self.comps.iter().map(|(f, m)| {
+            if m == old {
+                (*f, new.clone())
             } else {
-                i1 += 1;
-                i2 += 1;
+                (*f, Gas::Molecule(*m))
+            }
+        });
+        Mixture::from_unnormalized(comps)
+    }
+
+    /// The components of the mixture, with their molar fraction.
+    ///
+    /// Components are merged and sorted by decreasing molar fraction.
+    pub fn components(&self) -> impl Iterator<Item = (f64, &Molecule)> {
+        self.comps.iter().map(|(f, m)| (*f, m))
+    }
+
+    /// The molar fraction of a molecule in the mixture,
+    /// or `None` if the molecule isn't part of the mixture.
+    pub fn mole_fraction_of(&self, m: &Molecule) -> Option<f64> {
+        self.comps
+            .iter()
+            .find(|(_, cm)| cm == m)
+            .map(|(f, _)| *f)
+    }
+
+    /// Whether both mixtures have the same components, with molar fractions and molecules
+    /// equal within the relative tolerance `rtol` (see [`Molecule::approx_eq`]).
+    ///
+    /// Unlike `==`, this is robust to the rounding errors of mixture computations.
+    pub fn approx_eq(&self, other: &Mixture, rtol: f64) -> bool {
+        self.comps.len() == other.comps.len()
+            && self
+                .comps
+                .iter()
+                .zip(&other.comps)
+                .all(|((fl, ml), (fr, mr))| approx_eq(*fl, *fr, rtol) && ml.approx_eq(mr, rtol))
+    }
+
+    /// A deterministic key identifying the mixture, suitable for `HashMap` keys.
+    ///
+    /// Two mixtures have the same key if and only if they are equal,
+    /// regardless of the order they were built in.
+    pub fn canonical_key(&self) -> String {
+        let mut key = String::new();
+        for (idx, (f, m)) in self.comps.iter().enumerate() {
+            if idx > 0 {
+                key.push('+');
+            }
+            // f64 display is the shortest exact representation
+            match compounds::symbol(m) {
+                Some(symbol) => key += &format!("{f}:{symbol}"),
+                None => key += &format!("{f}:{m:?}"),
             }
         }
+        key
+    }
 
-        debug_assert!(comps.iter().map(|(f, _)| *f).sum::<f64>() > 0.9999999);
-        debug_assert!(comps.iter().map(|(f, _)| *f).sum::<f64>() < 1.0000001);
+    /// The partial state of each component of the mixture at the total pressure `p` and temperature `t`,
+    /// that is its partial pressure in Pa according to Dalton's law (molar fraction × `p`).
+    ///
+    /// The Dalton partial pressure depends neither on the equation of state `E` nor on `t`.
+    /// The fugacity of each component in the real mixture is the partial pressure
+    /// times the coefficient of [`Mixture::component_fugacity_coeffs`] with the same arguments.
+    pub fn partial_states<E: EquationOfState>(&self, p: f64, _t: f64) -> Vec<(Molecule, f64)> {
+        self.comps.iter().map(|(f, m)| (*m, f * p)).collect()
+    }
 
-        Ok(Mixture { comps })
+    /// The molar mass of each component of the mixture, in kg/mol
+    pub fn component_molar_masses(&self) -> Vec<(Molecule, f64)> {
+        self.comps.iter().map(|(_, m)| (*m, m.m)).collect()
+    }
+
+    /// The molar mass of the mixture, in kg/mol
+    pub fn molar_mass(&self) -> f64 {
+        self.comps.iter().fold(0.0, |s, (f, m)| s + f * m.m)
+    }
+
+    /// The pseudo-critical state of the mixture.
+    ///
+    /// Computed with Kay's rule, that is the molar fraction weighted average
+    /// of the critical pressure, volume and temperature of the components.
+    pub fn pseudo_critical(&self) -> Pvt {
+        self.comps.iter().fold(
+            Pvt {
+                p: 0.0,
+                v: 0.0,
+                t: 0.0,
+            },
+            |cs, (f, m)| Pvt {
+                p: cs.p + f * m.critical_state.p,
+                v: cs.v + f * m.critical_state.v,
+                t: cs.t + f * m.critical_state.t,
+            },
+        )
+    }
+
+    /// The pseudo-critical state of the mixture, computed with the given rule
+    pub fn pseudo_critical_with(&self, rule: PseudoCriticalRule) -> Pvt {
+        match rule {
+            PseudoCriticalRule::Kay => self.pseudo_critical(),
+            PseudoCriticalRule::LeeKesler => {
+                use crate::eos::{LeeKeslerParams, MixingRules};
+
+                let params = self.comps.iter().map(|(f, m)| {
+                    let params = LeeKeslerParams {
+                        tc: m.critical_state.t,
+                        pc: m.critical_state.p,
+                        w: m.acentric_factor(),
+                        options: crate::SolverOptions::default(),
+                    };
+                    (*f, params)
+                });
+                let LeeKeslerParams { tc, pc, w, .. } = LeeKeslerParams::mix(params);
+                Pvt {
+                    p: pc,
+                    v: (0.2905 - 0.085 * w) * crate::R * tc / pc,
+                    t: tc,
+                }
+            }
+        }
+    }
+
+    /// The pseudo acentric factor of the mixture, that is the molar fraction
+    /// weighted average of the acentric factor of the components
+    pub fn pseudo_acentric_factor(&self) -> f64 {
+        self.comps
+            .iter()
+            .fold(0.0, |w, (f, m)| w + f * m.acentric_factor())
+    }
+
+    /// The pseudo-critical compression factor Zc = PcVc/RTc
+    pub fn critical_compressibility(&self) -> f64 {
+        self.pseudo_critical().z()
+    }
+
+    /// The pseudo-reduced temperature Tr = T/Tc
+    pub fn reduced_temperature(&self, t: f64) -> f64 {
+        t / self.pseudo_critical().t
+    }
+
+    /// The pseudo-reduced pressure Pr = P/Pc
+    pub fn reduced_pressure(&self, p: f64) -> f64 {
+        p / self.pseudo_critical().p
     }
 }
 
+fn approx_eq(lhs: f64, rhs: f64, rtol: f64) -> bool {
+    (lhs - rhs).abs() <= rtol * lhs.abs().max(rhs.abs())
+}
+
+/// The rule to compute the pseudo-critical properties of a mixture,
+/// used by corresponding states methods
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum PseudoCriticalRule {
+    /// Molar fraction weighted average of the critical properties
+    #[default]
+    Kay,
+    /// Mixing rules of Lee and Kesler, weighting critical volumes
+    LeeKesler,
+}
+
 /// A generic gas, that can be either a molecule or a mixture.
 #[derive(Debug, Clone, PartialEq)]
 pub enum Gas {
@@ -156,6 +845,86 @@ pub enum Gas {
     Mixture(Mixture),
 }
 
+impl Gas {
+    /// Whether the gas is a single molecule
+    pub fn is_pure(&self) -> bool {
+        matches!(self, Gas::Molecule(_))
+    }
+
+    /// The molecule of a pure gas
+    pub fn as_molecule(&self) -> Option<&Molecule> {
+        match self {
+            Gas::Molecule(m) => Some(m),
+            Gas::Mixture(_) => None,
+        }
+    }
+
+    /// The mixture of a gas that is not pure
+    pub fn as_mixture(&self) -> Option<&Mixture> {
+        match self {
+            Gas::Molecule(_) => None,
+            Gas::Mixture(m) => Some(m),
+        }
+    }
+
+    /// Mix the amount `a.1` of gas `a.0` with the amount `b.1` of gas `b.0`, amounts in mol.
+    ///
+    /// Mixtures are flattened and identical molecules are merged,
+    /// e.g. 1 mol of O2 with 4 mol of N2 gives 20% O2 and 80% N2.
+    /// Amounts that are not positive are rejected with [`MixtureError::InvalidFraction`].
+    pub fn combine(a: (&Gas, f64), b: (&Gas, f64)) -> Result<Gas, MixtureError> {
+        let ((ga, na), (gb, nb)) = (a, b);
+        if let Some(n) = [na, nb].into_iter().find(|n| !n.is_finite() || *n <= 0.0) {
+            return Err(MixtureError::InvalidFraction(n));
+        }
+        MixtureBuilder::new()
+            .add(na / (na + nb), ga.clone())
+            .add_remainder(gb.clone())
+            .build()
+            .map(Gas::Mixture)
+    }
+
+    /// The molar mass of the gas, in kg/mol
+    pub fn molar_mass(&self) -> f64 {
+        match self {
+            Gas::Molecule(m) => m.molar_mass(),
+            Gas::Mixture(m) => m.molar_mass(),
+        }
+    }
+
+    /// The critical state of a molecule, or the pseudo-critical state of a mixture
+    pub fn critical_state(&self) -> Pvt {
+        match self {
+            Gas::Molecule(m) => m.critical_state,
+            Gas::Mixture(m) => m.pseudo_critical(),
+        }
+    }
+
+    /// The (pseudo-)critical compression factor Zc = PcVc/RTc
+    pub fn critical_compressibility(&self) -> f64 {
+        self.critical_state().z()
+    }
+
+    /// The (pseudo-)reduced temperature Tr = T/Tc
+    pub fn reduced_temperature(&self, t: f64) -> f64 {
+        t / self.critical_state().t
+    }
+
+    /// The (pseudo-)reduced pressure Pr = P/Pc
+    pub fn reduced_pressure(&self, p: f64) -> f64 {
+        p / self.critical_state().p
+    }
+}
+
+impl fmt::Display for Gas {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Gas::Molecule(m) => m.fmt(f),
+            Gas::Mixture(m) => m.fmt(f),
+        }
+    }
+}
+
 impl From<Molecule> for Gas {
     fn from(value: Molecule) -> Self {
         Gas::Molecule(value)
@@ -189,12 +958,27 @@ impl From<&Mixture> for Gas {
     }
 }
 
+/// An error occuring while parsing a gas.
+///
+/// Errors relative to a part of the parsed string report its byte span.
 #[derive(Debug, Clone, PartialEq)]
 pub enum GasParseError {
-    UnknownMolecule(String),
+    UnknownMolecule { symbol: String, span: Range<usize> },
     Mixture(MixtureError),
-    Float(ParseFloatError),
-    Other(String),
+    Float { err: ParseFloatError, span: Range<usize> },
+    InvalidComponent { component: String, span: Range<usize> },
+}
+
+impl GasParseError {
+    /// The byte span of the parsed string where the error occured, if any
+    pub fn span(&self) -> Option<Range<usize>> {
+        match self {
+            GasParseError::UnknownMolecule { span, .. } => Some(span.clone()),
+            GasParseError::Mixture(_) => None,
+            GasParseError::Float { span, .. } => Some(span.clone()),
+            GasParseError::InvalidComponent { span, .. } => Some(span.clone()),
+        }
+    }
 }
 
 impl From<MixtureError> for GasParseError {
@@ -202,83 +986,151 @@ impl From<MixtureError> for GasParseError {
         GasParseError::Mixture(value)
     }
 }
-impl From<ParseFloatError> for GasParseError {
-    fn from(value: ParseFloatError) -> Self {
-        GasParseError::Float(value)
-    }
-}
 
 impl fmt::Display for GasParseError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
-            GasParseError::UnknownMolecule(m) => write!(f, "Can't lookup {m} as a known molecule"),
+            GasParseError::UnknownMolecule { symbol, span } => {
+                write!(f, "Can't lookup {symbol} as a known molecule (at {span:?})")?;
+                if let Some(suggestion) = compounds::suggest(symbol) {
+                    write!(f, ", did you mean {suggestion}?")?;
+                }
+                Ok(())
+            }
             GasParseError::Mixture(m) => m.fmt(f),
-            GasParseError::Float(err) => err.fmt(f),
-            GasParseError::Other(msg) => write!(f, "{msg}"),
+            GasParseError::Float { err, span } => write!(f, "{err} (at {span:?})"),
+            GasParseError::InvalidComponent { component, span } => {
+                write!(f, "Can't parse {component} as a compound fraction (at {span:?})")
+            }
         }
     }
 }
 
-impl std::error::Error for GasParseError {}
+impl core::error::Error for GasParseError {}
 
-impl FromStr for Gas {
+/// Parse a mixture component such as "78.08%N2" or "CO2".
+/// `offset` is the byte offset of `sc` in the whole parsed string.
+fn parse_comp(sc: &str, offset: usize, compounds: &Compounds) -> Result<Comp, GasParseError> {
+    // surrounding whitespaces are allowed, e.g. "10ppm CO2 + N2"
+    let offset = offset + sc.len() - sc.trim_start().len();
+    let sc = sc.trim();
+
+    let lookup = |symbol: &str, start: usize| {
+        compounds.lookup(symbol).ok_or_else(|| GasParseError::UnknownMolecule {
+            symbol: symbol.to_string(),
+            span: start..start + symbol.len(),
+        })
+    };
+
+    // the first fraction unit found, with its position and scale
+    let unit = [("%", 1e-2), ("ppm", 1e-6), ("ppb", 1e-9)]
+        .into_iter()
+        .filter_map(|(unit, scale)| sc.find(unit).map(|idx| (idx, unit, scale)))
+        .min_by_key(|(idx, ..)| *idx);
+
+    let Some((idx, unit, scale)) = unit else {
+        return Ok(Comp::Remainder(lookup(sc, offset)?));
+    };
+    let sfrac = sc[..idx].trim_end();
+    let rest = &sc[idx + unit.len()..];
+    if rest.contains('%') || rest.contains("ppm") || rest.contains("ppb") {
+        return Err(GasParseError::InvalidComponent {
+            component: sc.to_string(),
+            span: offset..offset + sc.len(),
+        });
+    }
+
+    let frac = sfrac.parse::<f64>().map_err(|err| GasParseError::Float {
+        err,
+        span: offset..offset + sfrac.len(),
+    })? * scale;
+    let offset = offset + idx + unit.len();
+
+    // "%w", "ppmw" or "ppbw" denote a mass fraction, also when spaced such as "% w"
+    let spaced = rest.len() - rest.trim_start().len();
+    let (mass, rest, offset) = match rest.trim_start().strip_prefix('w') {
+        Some(rest) => (true, rest, offset + spaced + 1),
+        None => (false, rest, offset),
+    };
+    let symbol = rest.trim_start();
+    let offset = offset + rest.len() - symbol.len();
+    let gas = lookup(symbol, offset)?;
+    if mass {
+        Ok(Comp::MassFactor(frac, gas))
+    } else {
+        Ok(Comp::Factor(frac, gas))
+    }
+}
+
+/// Parses a single component of a mixture, e.g. "78.08%N2", "10%wCO2", "5ppm H2O"
+/// or "CO2" for the remainder
+impl FromStr for Comp {
     type Err = GasParseError;
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let scomps: Vec<&str> = s.split("+").collect();
+        parse_comp(s, 0, &Compounds::new())
+    }
+}
 
-        if scomps.is_empty() {
-            Err(GasParseError::Mixture(MixtureError::MixtureNotWhole))
-        } else if scomps.len() == 1 {
-            compounds::lookup(&scomps[0])
-                .ok_or_else(|| GasParseError::UnknownMolecule(scomps[0].to_string()))
-        } else {
-            let mut mcomps = Vec::<Comp>::new();
-            for sc in scomps {
-                let sfrac: Vec<&str> = sc.split("%").collect();
-                if sfrac.len() > 2 {
-                    return Err(GasParseError::Other(format!("Can't parse {sc} as a compound fraction")));
-                }
-                let symbol = *sfrac.iter().last().unwrap();
-                let g = compounds::lookup(symbol)
-                    .ok_or_else(|| GasParseError::UnknownMolecule(symbol.to_string()))?;
-                if sfrac.len() == 1 {
-                    mcomps.push(Comp::Remainder(g));
-                } else {
-                    let frac = sfrac[0]
-                        .parse::<f64>()?;
-                    mcomps.push(Comp::Factor(frac / 100.0, g));
-                }
-            }
+impl TryFrom<&str> for Comp {
+    type Error = GasParseError;
+    fn try_from(s: &str) -> Result<Self, Self::Error> {
+        s.parse()
+    }
+}
 
-            Ok(Gas::Mixture(Mixture::new(mcomps)?))
+/// Parses a gas with the grammar described in [`Gas::from_str`],
+/// looking up the symbols in `compounds`
+pub(crate) fn parse_gas(s: &str, compounds: &Compounds) -> Result<Gas, GasParseError> {
+    let scomps: Vec<&str> = s.split("+").collect();
+
+    if scomps.is_empty() {
+        Err(GasParseError::Mixture(MixtureError::Underfilled(0.0)))
+    } else if scomps.len() == 1 {
+        let symbol = s.trim();
+        let start = s.len() - s.trim_start().len();
+        compounds.lookup(symbol).ok_or_else(|| GasParseError::UnknownMolecule {
+            symbol: symbol.to_string(),
+            span: start..start + symbol.len(),
+        })
+    } else {
+        let mut mcomps = Vec::<Comp>::new();
+        let mut offset = 0;
+        for sc in scomps {
+            mcomps.push(parse_comp(sc, offset, compounds)?);
+            offset += sc.len() + 1;
         }
+
+        Ok(Gas::Mixture(Mixture::new(mcomps)?))
     }
 }
 
-#[cfg(test)]
+/// Parses a single molecule symbol such as "N2", or a mixture of components
+/// separated by `+`, such as "78.08%N2+20.95%O2+0.93%Ar+CO2" (see [`Comp::from_str`]).
+/// Whitespace around the components, fractions and units is ignored,
+/// so that "78.08 % N2 + 20.95 % O2 + 0.93 % Ar + CO2" is parsed the same way.
+impl FromStr for Gas {
+    type Err = GasParseError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse_gas(s, &Compounds::new())
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
 mod tests {
-    use super::{Comp, Gas, Mixture};
-    use crate::{Molecule, State, compounds, eos::PengRobinson, gas::MixtureError};
+    use super::{Comp, Gas, GasParseError, Mixture, MixtureBuilder, PseudoCriticalRule, Validity};
+    use crate::{
+        Molecule, State, compounds,
+        eos::{IdealGas, LeeKesler, PengRobinson, VanDerWaals},
+        gas::MixtureError,
+    };
     use float_eq::assert_float_eq;
 
     fn assert_molecule_eq(lhs: &Molecule, rhs: &Molecule, rtol: f64) {
-        assert_float_eq!(lhs.m, rhs.m, r1st <= rtol);
-        assert_float_eq!(lhs.critical_state.p, rhs.critical_state.p, r1st <= rtol);
-        assert_float_eq!(lhs.critical_state.v, rhs.critical_state.v, r1st <= rtol);
-        assert_float_eq!(lhs.critical_state.t, rhs.critical_state.t, r1st <= rtol);
-        assert_float_eq!(lhs.w, rhs.w, r1st <= rtol);
+        assert!(lhs.approx_eq(rhs, rtol), "assertion failed: {lhs:?} != {rhs:?}");
     }
 
     fn assert_mixture_eq(lhs: &Mixture, rhs: &Mixture, rtol: f64) {
-        if lhs.comps.len() != rhs.comps.len() {
-            panic!("assertion failed: lhs and rhs are mixtures with different components count");
-        }
-        for idx in 0..lhs.comps.len() {
-            let cl = &lhs.comps[idx];
-            let cr = &rhs.comps[idx];
-            assert_float_eq!(cl.0, cr.0, r1st <= rtol);
-            assert_molecule_eq(&cl.1, &cr.1, rtol);
-        }
+        assert!(lhs.approx_eq(rhs, rtol), "assertion failed: {lhs} != {rhs}");
     }
 
     fn assert_gas_eq(lhs: &Gas, rhs: &Gas, rtol: f64) {
@@ -328,10 +1180,482 @@ mod tests {
     }
 
     #[test]
-    fn mixture_new_reports_mixture_not_whole() {
+    fn acentric_factor_estimated_from_zc() {
+        for m in [
+            compounds::CH4,
+            compounds::C4H10,
+            compounds::C6H6,
+            compounds::C6H12,
+            compounds::CO2,
+            compounds::SO2,
+        ] {
+            assert_float_eq!(m.estimated_acentric_factor(), m.w.unwrap(), abs <= 0.05);
+            assert_eq!(Some(m.acentric_factor()), m.w);
+        }
+
+        // PR still runs without the acentric factor
+        let unknown = Molecule {
+            w: None,
+            ..compounds::C4H10
+        };
+        assert_eq!(unknown.acentric_factor(), unknown.estimated_acentric_factor());
+        let z = unknown.z::<PengRobinson>(5e5, 450.0);
+        let z_ref = compounds::C4H10.z::<PengRobinson>(5e5, 450.0);
+        assert_float_eq!(z, z_ref, r2nd <= 0.01);
+
+        // and is still equal to itself
+        assert_eq!(unknown, unknown);
+        assert!(unknown.approx_eq(&unknown, 1e-9));
+        assert!(!unknown.approx_eq(&compounds::C4H10, 1e-9));
+        let mix = Mixture::new([Comp::Factor(0.5, unknown.into()), Comp::Remainder(unknown.into())]).unwrap();
+        assert_eq!(mix.components().count(), 1);
+        assert_eq!(mix.mole_fraction_of(&unknown), Some(1.0));
+        assert!(unknown.to_string().ends_with(", w unknown}"));
+    }
+
+    #[test]
+    fn gas_kind() {
+        let n2: Gas = "N2".parse().unwrap();
+        assert!(n2.is_pure());
+        assert_eq!(n2.as_molecule(), Some(&compounds::N2));
+        assert_eq!(n2.as_mixture(), None);
+
+        let air: Gas = "dry_air".parse().unwrap();
+        assert!(!air.is_pure());
+        assert_eq!(air.as_molecule(), None);
+        assert_eq!(air.as_mixture(), Some(&compounds::dry_air()));
+    }
+
+    #[test]
+    fn from_critical() {
+        let ar = Molecule::from_critical(150.8, 48.7 * 1e5, 74.9 * 1e-6, 0.001, 0.039948);
+        assert_eq!(
+            ar,
+            Molecule {
+                validity: None,
+                cp: None,
+                ..compounds::AR
+            }
+        );
+        assert_eq!(ar.z::<PengRobinson>(100e5, 200.0), compounds::AR.z::<PengRobinson>(100e5, 200.0));
+    }
+
+    #[test]
+    fn validity_range() {
+        let h2o = compounds::H2O;
+        assert_eq!(h2o.validity(1e5, 250.0), Validity::OutOfRange);
+        assert_eq!(h2o.validity(1e5, 300.0), Validity::InRange);
+        assert_eq!(h2o.validity(2000e6, 300.0), Validity::OutOfRange);
+        assert_eq!(compounds::XE.validity(1e5, 300.0), Validity::Unknown);
+    }
+
+    #[test]
+    fn nitrogen_boyle_temperature() {
+        let n2 = compounds::N2;
+        let tc = n2.critical_state.t;
+
+        // experimental value is 327 K, or about 2.6 Tc
+        let tb = n2.boyle_temperature::<PengRobinson>().unwrap();
+        assert!(tb > 2.0 * tc && tb < 4.0 * tc);
+
+        // Van der Waals predicts exactly 27/8 Tc
+        let tb = n2.boyle_temperature::<VanDerWaals>().unwrap();
+        assert_float_eq!(tb, 27.0 / 8.0 * tc, r2nd <= 1e-9);
+
+        assert_eq!(n2.boyle_temperature::<IdealGas>(), None);
+    }
+
+    #[test]
+    fn z_corresponding_states() {
+        let ch4 = compounds::CH4;
+        for (p, t) in [(1e5, 300.0), (50e5, 250.0), (200e5, 350.0)] {
+            let z = ch4.z_corresponding_states(p, t);
+            assert_float_eq!(z, ch4.z::<LeeKesler>(p, t), r2nd <= 1e-12);
+            assert_float_eq!(z, ch4.z::<PengRobinson>(p, t), r2nd <= 0.05);
+        }
+    }
+
+    #[test]
+    fn z_sensitivity_to_acentric_factor() {
+        let co2 = compounds::CO2;
+        let (p, t) = (50e5, 300.0);
+        assert_eq!(co2.dz_dw::<IdealGas>(p, t), 0.0);
+
+        // below Tc, a larger ω increases the attraction and decreases Z
+        let dz_dw = co2.dz_dw::<PengRobinson>(p, t);
+        assert!(dz_dw < 0.0);
+        let dw = 0.01;
+        let shifted = Molecule {
+            w: Some(co2.acentric_factor() + dw),
+            ..co2
+        };
+        let dz = shifted.z::<PengRobinson>(p, t) - co2.z::<PengRobinson>(p, t);
+        assert_float_eq!(dz_dw * dw, dz, r2nd <= 0.01);
+
+        // the quantum correction doesn't use the acentric factor
+        let h2q = compounds::H2.with_quantum_correction(compounds::H2_QUANTUM_CORRECTION);
+        let (p, t) = (100e5, 50.0);
+        assert_eq!(h2q.dz_dw::<PengRobinson>(p, t), 0.0);
+        let shifted = Molecule {
+            w: Some(h2q.acentric_factor() + dw),
+            ..h2q
+        };
+        assert_eq!(shifted.z::<PengRobinson>(p, t), h2q.z::<PengRobinson>(p, t));
+    }
+
+    #[test]
+    fn dry_air_components() {
+        let air = compounds::dry_air();
+        let comps: Vec<(f64, Molecule)> = air.components().map(|(f, m)| (f, *m)).collect();
+        assert_eq!(
+            comps,
+            vec![
+                (0.7808, compounds::N2),
+                (0.2095, compounds::O2),
+                (0.0093, compounds::AR),
+                (0.0004, compounds::CO2),
+            ]
+        );
+        assert_eq!(air.mole_fraction_of(&compounds::O2), Some(0.2095));
+        assert_eq!(air.mole_fraction_of(&compounds::H2), None);
+    }
+
+    #[test]
+    fn display_dry_air_round_trips() {
+        let air = Gas::from(compounds::dry_air());
+        let s = air.to_string();
+        assert_eq!(s, "78.08%N2+20.95%O2+0.93%Ar+CO2");
+
+        let parsed: Gas = s.parse().expect("should parse displayed air");
+        assert_gas_eq(&parsed, &air, 1e-12);
+
+        assert_eq!(Gas::from(compounds::N2).to_string(), "N2");
+        assert_eq!(format!("{:.1}", compounds::dry_air()), "78.1%N2+20.9%O2+0.9%Ar+CO2");
+    }
+
+    #[test]
+    fn mass_fractions_convert_to_molar_fractions() {
+        let air = Mixture::from_mass_fractions(&[
+            Comp::MassFactor(0.7552, compounds::N2.into()),
+            Comp::MassFactor(0.2314, compounds::O2.into()),
+            Comp::MassFactor(0.0129, compounds::AR.into()),
+            Comp::Remainder(compounds::CO2.into()),
+        ])
+        .unwrap();
+        let expected = Mixture::new(&[
+            Comp::Factor(0.7808559254579436, compounds::N2.into()),
+            Comp::Factor(0.20946159881181467, compounds::O2.into()),
+            Comp::Factor(0.009353398130184572, compounds::AR.into()),
+            Comp::Remainder(compounds::CO2.into()),
+        ])
+        .unwrap();
+        assert_mixture_eq(&air, &expected, 1e-9);
+
+        let parsed: Gas = "75.52%wN2+23.14%wO2+1.29%wAr+CO2".parse().unwrap();
+        assert_gas_eq(&parsed, &air.into(), 1e-12);
+
+        let res = "78%N2+21%wO2+Ar".parse::<Gas>();
+        assert_eq!(res, Err(GasParseError::Mixture(MixtureError::MixedBasis)));
+    }
+
+    #[test]
+    fn parse_reports_unknown_molecule_position() {
+        let err = "78%N2+21%Ooops".parse::<Gas>().unwrap_err();
+        assert_eq!(
+            err,
+            GasParseError::UnknownMolecule {
+                symbol: "Ooops".to_string(),
+                span: 9..14,
+            }
+        );
+        assert!(err.to_string().contains("Ooops"));
+        assert!(err.to_string().contains("9..14"));
+
+        let err = "78%N2+21%CO22+Ar".parse::<Gas>().unwrap_err();
+        assert_eq!(err.span(), Some(9..13));
+        assert!(err.to_string().ends_with("did you mean CO2?"));
+
+        let err = "78%wN2+2x%wO2+Ar".parse::<Gas>().unwrap_err();
+        assert_eq!(err.span(), Some(7..9));
+    }
+
+    #[test]
+    fn parse_component() {
+        let n2: Gas = compounds::N2.into();
+        let co2: Gas = compounds::CO2.into();
+        match "78.08%N2".parse::<Comp>().unwrap() {
+            Comp::Factor(f, g) => {
+                assert_float_eq!(f, 0.7808, ulps <= 1);
+                assert_eq!(g, n2);
+            }
+            comp => panic!("unexpected component {comp:?}"),
+        }
+        assert_eq!(Comp::try_from("CO2").unwrap(), Comp::Remainder(co2.clone()));
+        assert_eq!("10%wCO2".parse::<Comp>().unwrap(), Comp::MassFactor(0.1, co2));
+
+        let err = "7a%N2".parse::<Comp>().unwrap_err();
+        assert!(matches!(err, GasParseError::Float { .. }));
+        assert_eq!(err.span(), Some(0..2));
+
+        let err = "10%Xx".parse::<Comp>().unwrap_err();
+        assert!(matches!(err, GasParseError::UnknownMolecule { .. }));
+        assert_eq!(err.span(), Some(3..5));
+
+        let err = "10%20%N2".parse::<Comp>().unwrap_err();
+        assert!(matches!(err, GasParseError::InvalidComponent { .. }));
+    }
+
+    #[test]
+    fn parse_ignores_whitespace() {
+        let canonical: Gas = "78.08%N2+20.95%O2+0.93%Ar+CO2".parse().unwrap();
+        for s in [
+            "78.08 % N2 + 20.95 % O2 + 0.93 % Ar + CO2",
+            " 78.08%N2 +20.95%O2+ 0.93%Ar+CO2 ",
+            "78.08\t%\tN2\t+\t20.95 %O2 + 0.93% Ar + CO2",
+        ] {
+            let parsed: Gas = s.parse().unwrap();
+            assert_gas_eq(&parsed, &canonical, 0.0);
+        }
+
+        let canonical: Gas = "75.52%wN2+23.14%wO2+1.29%wAr+CO2".parse().unwrap();
+        let parsed: Gas = "75.52 % w N2 + 23.14 %w O2 + 1.29% w Ar + CO2".parse().unwrap();
+        assert_gas_eq(&parsed, &canonical, 0.0);
+
+        let err = "78 % N2 + 21 % w Ooops + Ar".parse::<Gas>().unwrap_err();
+        assert_eq!(err.span(), Some(17..22));
+    }
+
+    #[test]
+    fn parse_trace_components() {
+        let gas: Gas = "10ppm CO2 + N2".parse().unwrap();
+        let Gas::Mixture(mix) = gas else {
+            panic!("expected a mixture");
+        };
+        assert_float_eq!(mix.mole_fraction_of(&compounds::CO2).unwrap(), 1e-5, r2nd <= 1e-12);
+        assert_float_eq!(mix.mole_fraction_of(&compounds::N2).unwrap(), 1.0 - 1e-5, r2nd <= 1e-12);
+
+        let gas: Gas = "5ppbH2O+1%Ar+N2".parse().unwrap();
+        let Gas::Mixture(mix) = gas else {
+            panic!("expected a mixture");
+        };
+        assert_float_eq!(mix.mole_fraction_of(&compounds::H2O).unwrap(), 5e-9, r2nd <= 1e-12);
+        assert_float_eq!(mix.mole_fraction_of(&compounds::AR).unwrap(), 0.01, r2nd <= 1e-12);
+        assert_float_eq!(mix.mole_fraction_of(&compounds::N2).unwrap(), 0.99 - 5e-9, r2nd <= 1e-12);
+
+        let err = "10ppm Xx + N2".parse::<Gas>().unwrap_err();
+        assert_eq!(err.span(), Some(6..8));
+
+        let err = "0ppm CO2 + N2".parse::<Gas>().unwrap_err();
+        assert!(matches!(err, GasParseError::Mixture(MixtureError::InvalidFraction(_))));
+    }
+
+    #[test]
+    fn component_molar_masses_of_air() {
+        let air = compounds::dry_air();
+        let masses = air.component_molar_masses();
+        assert_eq!(masses[0], (compounds::N2, compounds::N2.molar_mass()));
+        let sum: f64 = air.components().zip(&masses).map(|((f, _), (_, m))| f * m).sum();
+        assert_float_eq!(sum, air.molar_mass(), r2nd <= 1e-15);
+    }
+
+    #[test]
+    fn dilute_air_with_argon() {
+        let air = compounds::dry_air();
+        let diluted = air.dilute(compounds::AR, 0.1).unwrap();
+        let expected = [
+            (compounds::N2, 0.9 * 0.7808),
+            (compounds::O2, 0.9 * 0.2095),
+            (compounds::AR, 0.1 + 0.9 * 0.0093),
+            (compounds::CO2, 0.9 * 0.0004),
+        ];
+        assert_eq!(diluted.components().count(), expected.len());
+        for (m, f) in expected {
+            assert_float_eq!(diluted.mole_fraction_of(&m).unwrap(), f, r2nd <= 1e-12);
+        }
+
+        assert_eq!(air.dilute(compounds::AR, 1.0), Err(MixtureError::InvalidFraction(1.0)));
+        assert_eq!(air.dilute(compounds::AR, 0.0), Err(MixtureError::InvalidFraction(0.0)));
+    }
+
+    #[test]
+    fn remove_argon_from_air() {
+        let air = compounds::dry_air();
+        let no_ar = air.without(&compounds::AR).unwrap();
+        assert_eq!(no_ar.mole_fraction_of(&compounds::AR), None);
+        for m in [compounds::N2, compounds::O2, compounds::CO2] {
+            let f = air.mole_fraction_of(&m).unwrap() / (1.0 - 0.0093);
+            assert_float_eq!(no_ar.mole_fraction_of(&m).unwrap(), f, r2nd <= 1e-12);
+        }
+
+        assert_eq!(no_ar.without(&compounds::AR), Ok(no_ar.clone()));
+        let n2 = Mixture::new([Comp::Remainder(compounds::N2.into())]).unwrap();
+        assert_eq!(n2.without(&compounds::N2), Err(MixtureError::Underfilled(0.0)));
+    }
+
+    #[test]
+    fn substitute_oxygen() {
+        let air = compounds::dry_air();
+        let heliox = air.substitute(&compounds::O2, compounds::HE).unwrap();
+        assert_eq!(heliox.mole_fraction_of(&compounds::O2), None);
+        assert_float_eq!(heliox.mole_fraction_of(&compounds::HE).unwrap(), 0.2095, r2nd <= 1e-12);
+        assert_float_eq!(heliox.mole_fraction_of(&compounds::N2).unwrap(), 0.7808, r2nd <= 1e-12);
+
+        // substituting with a molecule of the mixture merges them
+        let merged = air.substitute(&compounds::O2, compounds::N2).unwrap();
+        assert_eq!(merged.components().count(), 3);
+        assert_float_eq!(merged.mole_fraction_of(&compounds::N2).unwrap(), 0.9903, r2nd <= 1e-12);
+    }
+
+    #[test]
+    fn mixture_builder() {
+        let mut builder = MixtureBuilder::new();
+        for (f, m) in [
+            (0.7808, compounds::N2),
+            (0.2095, compounds::O2),
+            (0.0093, compounds::AR),
+            (0.0004, compounds::CO2),
+        ] {
+            builder = builder.add(f, m);
+        }
+        assert_eq!(builder.build(), Ok(compounds::dry_air()));
+
+        let res = MixtureBuilder::new()
+            .add(0.1, compounds::CO2)
+            .add_remainder(compounds::N2)
+            .add_remainder(compounds::O2)
+            .build();
+        assert_eq!(res, Err(MixtureError::MultipleRemainders));
+
+        let res = MixtureBuilder::new().add(0.5, compounds::N2).build();
+        assert_eq!(res, Err(MixtureError::Underfilled(0.5)));
+    }
+
+    #[test]
+    fn unnormalized_fractions() {
+        let mix = Mixture::from_unnormalized([
+            (0.78, compounds::N2.into()),
+            (0.209, compounds::O2.into()),
+            (0.008, compounds::AR.into()),
+        ])
+        .unwrap();
+        let total: f64 = mix.components().map(|(f, _)| f).sum();
+        assert_float_eq!(total, 1.0, abs <= 1e-12);
+        assert_float_eq!(mix.mole_fraction_of(&compounds::N2).unwrap(), 0.78 / 0.997, r2nd <= 1e-12);
+        assert_float_eq!(mix.mole_fraction_of(&compounds::AR).unwrap(), 0.008 / 0.997, r2nd <= 1e-12);
+
+        let pure = Mixture::from_unnormalized([(42.0, compounds::N2.into())]).unwrap();
+        assert_eq!(pure.mole_fraction_of(&compounds::N2), Some(1.0));
+
+        let res = Mixture::from_unnormalized([
+            (0.8, compounds::N2.into()),
+            (-0.2, compounds::O2.into()),
+        ]);
+        assert_eq!(res, Err(MixtureError::InvalidFraction(-0.2)));
+        assert!(matches!(Mixture::from_unnormalized([]), Err(MixtureError::Underfilled(_))));
+    }
+
+    #[test]
+    fn mixture_approx_eq() {
+        let a = Mixture::new([
+            Comp::Factor(0.8, compounds::N2.into()),
+            Comp::Remainder(compounds::O2.into()),
+        ])
+        .unwrap();
+        let mut b = a.clone();
+        b.comps[0].0 += 1e-12;
+        b.comps[1].0 -= 1e-12;
+        assert_ne!(a, b);
+        assert!(a.approx_eq(&b, 1e-9));
+        assert!(!a.approx_eq(&b, 1e-14));
+
+        let c = Mixture::new([
+            Comp::Factor(0.8, compounds::N2.into()),
+            Comp::Remainder(compounds::AR.into()),
+        ])
+        .unwrap();
+        assert!(!a.approx_eq(&c, 1e-9));
+        assert!(!a.approx_eq(&compounds::dry_air(), 1e-9));
+    }
+
+    #[test]
+    fn combine_mole_amounts() {
+        let o2: Gas = compounds::O2.into();
+        let n2: Gas = compounds::N2.into();
+        let gas = Gas::combine((&o2, 1.0), (&n2, 4.0)).unwrap();
+        let mix = gas.as_mixture().unwrap();
+        assert_float_eq!(mix.mole_fraction_of(&compounds::O2).unwrap(), 0.2, abs <= 1e-12);
+        assert_float_eq!(mix.mole_fraction_of(&compounds::N2).unwrap(), 0.8, abs <= 1e-12);
+
+        // nested mixtures are flattened and merged
+        let gas = Gas::combine((&gas, 5.0), (&n2, 5.0)).unwrap();
+        let mix = gas.as_mixture().unwrap();
+        assert_float_eq!(mix.mole_fraction_of(&compounds::O2).unwrap(), 0.1, abs <= 1e-12);
+        assert_float_eq!(mix.mole_fraction_of(&compounds::N2).unwrap(), 0.9, abs <= 1e-12);
+
+        let res = Gas::combine((&o2, 0.0), (&n2, 4.0));
+        assert_eq!(res, Err(MixtureError::InvalidFraction(0.0)));
+    }
+
+    #[test]
+    fn flatten_three_level_mixture() {
+        let inner = Mixture::new([
+            Comp::Factor(0.5, compounds::O2.into()),
+            Comp::Remainder(compounds::N2.into()),
+        ])
+        .unwrap();
+        let middle = Mixture::new([
+            Comp::Factor(0.4, inner.into()),
+            Comp::Remainder(compounds::CO2.into()),
+        ])
+        .unwrap();
+        let outer = Mixture::new([
+            Comp::Factor(0.5, middle.clone().into()),
+            Comp::Factor(0.3, compounds::AR.into()),
+            Comp::Remainder(compounds::N2.into()),
+        ])
+        .unwrap();
+
+        let expected = [
+            (compounds::N2, 0.5 * 0.4 * 0.5 + 0.2),
+            (compounds::CO2, 0.5 * 0.6),
+            (compounds::AR, 0.3),
+            (compounds::O2, 0.5 * 0.4 * 0.5),
+        ];
+        assert_eq!(outer.components().count(), expected.len());
+        for (m, f) in expected {
+            assert_float_eq!(outer.mole_fraction_of(&m).unwrap(), f, abs <= 1e-12);
+        }
+
+        // the intermediate mixture is already flat
+        assert!(middle.components().all(|(_, m)| *m != compounds::AR));
+        assert_eq!(middle.components().count(), 3);
+    }
+
+    #[test]
+    fn nan_fraction_is_invalid() {
+        let res = Mixture::new([
+            Comp::Factor(f64::NAN, compounds::N2.into()),
+            Comp::Factor(0.21, compounds::O2.into()),
+        ]);
+        assert!(matches!(res, Err(MixtureError::InvalidFraction(f)) if f.is_nan()));
+
+        let res = Mixture::new([
+            Comp::Factor(f64::NAN, compounds::N2.into()),
+            Comp::Remainder(compounds::O2.into()),
+        ]);
+        assert!(matches!(res, Err(MixtureError::InvalidFraction(f)) if f.is_nan()));
+
+        let res = Mixture::new([
+            Comp::MassFactor(f64::NAN, compounds::N2.into()),
+            Comp::Remainder(compounds::O2.into()),
+        ]);
+        assert!(matches!(res, Err(MixtureError::InvalidFraction(f)) if f.is_nan()));
+    }
+
+    #[test]
+    fn mixture_new_reports_underfilled() {
         fn assert(res: Result<Mixture, MixtureError>) {
-            assert!(res.is_err());
-            assert_eq!(res.unwrap_err(), MixtureError::MixtureNotWhole);
+            assert!(matches!(res, Err(MixtureError::Underfilled(_))));
         }
 
         assert(Mixture::new(&[]));
@@ -341,12 +1665,127 @@ mod tests {
             Comp::Factor(0.3, compounds::O2.into()),
             Comp::Factor(0.1, compounds::AR.into()),
         ]));
+    }
+
+    #[test]
+    fn mixture_new_reports_overfilled() {
+        fn assert(res: Result<Mixture, MixtureError>) {
+            assert!(matches!(res, Err(MixtureError::Overfilled(_))));
+        }
 
         assert(Mixture::new(&[
             Comp::Factor(0.5, compounds::N2.into()),
             Comp::Factor(0.5, compounds::O2.into()),
             Comp::Factor(0.1, compounds::AR.into()),
         ]));
+
+        // duplicated molecule
+        assert(Mixture::new(&[
+            Comp::Factor(0.6, compounds::N2.into()),
+            Comp::Factor(0.6, compounds::N2.into()),
+        ]));
+
+        // nothing left for the remainder
+        assert(Mixture::new(&[
+            Comp::Factor(0.5, compounds::N2.into()),
+            Comp::Factor(0.5, compounds::O2.into()),
+            Comp::Remainder(compounds::AR.into()),
+        ]));
+        assert(Mixture::new(&[
+            Comp::Factor(0.7, compounds::N2.into()),
+            Comp::Factor(0.4, compounds::O2.into()),
+            Comp::Remainder(compounds::AR.into()),
+        ]));
+    }
+
+    #[test]
+    fn reduced_properties() {
+        let n2 = compounds::N2;
+        assert_float_eq!(n2.reduced_temperature(300.0), 2.377179, r2nd <= 1e-6);
+        assert_float_eq!(n2.reduced_pressure(100e5), 2.949853, r2nd <= 1e-6);
+        assert_float_eq!(n2.critical_compressibility(), 0.290123, r2nd <= 1e-5);
+
+        let air = compounds::dry_air();
+        let cs = air.pseudo_critical();
+        // 0.7808*126.2 + 0.2095*154.6 + 0.0093*150.8 + 0.0004*304.1
+        assert_float_eq!(cs.t, 132.44974, r2nd <= 1e-6);
+        // 0.7808*33.9 + 0.2095*50.4 + 0.0093*48.7 + 0.0004*73.8
+        assert_float_eq!(cs.p, 37.51035e5, r2nd <= 1e-6);
+        assert_float_eq!(air.reduced_temperature(300.0), 300.0 / 132.44974, r2nd <= 1e-6);
+
+        let gas = Gas::from(air);
+        assert_float_eq!(gas.reduced_pressure(100e5), 100.0 / 37.51035, r2nd <= 1e-6);
+    }
+
+    #[test]
+    fn canonical_key_matches_equality() {
+        let a: Gas = "78.08%N2+20.95%O2+0.93%Ar+CO2".parse().unwrap();
+        let b: Gas = "0.93%Ar+20.95%O2+CO2+78.08%N2".parse().unwrap();
+        let c: Gas = "78%N2+21%O2+0.93%Ar+CO2".parse().unwrap();
+        let (Gas::Mixture(a), Gas::Mixture(b), Gas::Mixture(c)) = (a, b, c) else {
+            panic!("expected mixtures");
+        };
+        assert_eq!(a, b);
+        assert_eq!(a.canonical_key(), b.canonical_key());
+        assert_ne!(a.canonical_key(), c.canonical_key());
+
+        let custom = Molecule {
+            m: 0.030,
+            ..compounds::N2
+        };
+        let d = Mixture::new([
+            Comp::Factor(0.5, custom.into()),
+            Comp::Remainder(compounds::N2.into()),
+        ])
+        .unwrap();
+        assert_ne!(d.canonical_key(), a.canonical_key());
+
+        let mut cache = std::collections::HashMap::new();
+        cache.insert(a.canonical_key(), 1);
+        assert_eq!(cache.get(&b.canonical_key()), Some(&1));
+    }
+
+    #[test]
+    fn partial_states_of_dry_air() {
+        let air = compounds::dry_air();
+        let p = 101325.0;
+        let partials = air.partial_states::<PengRobinson>(p, 288.15);
+        assert_eq!(partials.len(), 4);
+        assert_eq!(partials[0].0, compounds::N2);
+        assert_float_eq!(partials[0].1, 0.7808 * p, r2nd <= 1e-12);
+        let sum: f64 = partials.iter().map(|(_, pp)| pp).sum();
+        assert_float_eq!(sum, p, r2nd <= 1e-12);
+    }
+
+    #[test]
+    fn inherent_molar_mass() {
+        let gas: Gas = "78%N2+21%O2+Ar".parse().unwrap();
+        assert_eq!(gas.molar_mass(), State::molar_mass(&gas));
+        assert_float_eq!(gas.molar_mass(), 0.02896, r2nd <= 1e-3);
+        if let Gas::Mixture(mix) = &gas {
+            assert_eq!(mix.molar_mass(), State::molar_mass(mix));
+        }
+        assert_eq!(compounds::N2.molar_mass(), State::molar_mass(&compounds::N2));
+    }
+
+    #[test]
+    fn pseudo_critical_rules() {
+        let air = compounds::dry_air();
+        // 0.7808*0.039 + 0.2095*0.025 + 0.0093*0.001 + 0.0004*0.239
+        assert_float_eq!(air.pseudo_acentric_factor(), 0.0357936, r2nd <= 1e-6);
+        assert_eq!(air.pseudo_critical_with(PseudoCriticalRule::Kay), air.pseudo_critical());
+
+        // close to Kay's rule for components of similar size
+        let kay = air.pseudo_critical();
+        let lk = air.pseudo_critical_with(PseudoCriticalRule::LeeKesler);
+        assert_float_eq!(lk.t, kay.t, r2nd <= 0.01);
+        assert_float_eq!(lk.p, kay.p, r2nd <= 0.05);
+
+        // a pure component is its own pseudo-critical state
+        let n2 = Mixture::new([Comp::Remainder(compounds::N2.into())]).unwrap();
+        let lk = n2.pseudo_critical_with(PseudoCriticalRule::LeeKesler);
+        assert_float_eq!(lk.t, compounds::N2.critical_state.t, r2nd <= 1e-12);
+        assert_float_eq!(lk.p, compounds::N2.critical_state.p, r2nd <= 1e-12);
     }
 
     #[test]
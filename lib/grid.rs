@@ -0,0 +1,242 @@
+use std::{
+    fmt,
+    io::{self, BufRead, Write},
+    num::ParseFloatError,
+};
+
+use crate::{IsothermSweep, State, eos::EquationOfState};
+
+/// A grid of compression factors, by temperature (rows) and pressure (columns).
+///
+/// The CSV representation has a header row with the pressures in bar,
+/// and one row per temperature in K, followed by the Z values.
+/// Missing values are empty or `nan` and read as NaN.
+/// ```text
+/// T,1,10,100
+/// 300,0.9998,0.998,0.9847
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct PtGrid {
+    t_label: String,
+    pressures: Vec<f64>,
+    temperatures: Vec<f64>,
+    z: Vec<Vec<f64>>,
+}
+
+/// An error reading a [`PtGrid`] from CSV
+#[derive(Debug)]
+pub enum PtGridError {
+    Io(io::Error),
+    /// The CSV has no header
+    Empty,
+    /// A field isn't a valid number
+    Float {
+        err: ParseFloatError,
+        line: usize,
+        column: usize,
+    },
+    /// A row doesn't have as many fields as the header
+    RowLength {
+        line: usize,
+        expected: usize,
+        found: usize,
+    },
+}
+
+impl fmt::Display for PtGridError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PtGridError::Io(err) => err.fmt(f),
+            PtGridError::Empty => write!(f, "The CSV data has no header"),
+            PtGridError::Float { err, line, column } => {
+                write!(f, "{err} (at line {line}, column {column})")
+            }
+            PtGridError::RowLength {
+                line,
+                expected,
+                found,
+            } => write!(f, "Expected {expected} fields at line {line}, found {found}"),
+        }
+    }
+}
+
+impl std::error::Error for PtGridError {}
+
+impl From<io::Error> for PtGridError {
+    fn from(err: io::Error) -> Self {
+        PtGridError::Io(err)
+    }
+}
+
+impl PtGrid {
+    /// A grid of pressures (in Pa) and temperatures (in K), with all Z values set to NaN
+    pub fn new(pressures: &[f64], temperatures: &[f64]) -> Self {
+        PtGrid {
+            t_label: "T".to_string(),
+            pressures: pressures.to_vec(),
+            temperatures: temperatures.to_vec(),
+            z: vec![vec![f64::NAN; pressures.len()]; temperatures.len()],
+        }
+    }
+
+    /// The pressures of the grid columns, in Pa
+    pub fn pressures(&self) -> &[f64] {
+        &self.pressures
+    }
+
+    /// The temperatures of the grid rows, in K
+    pub fn temperatures(&self) -> &[f64] {
+        &self.temperatures
+    }
+
+    /// The Z values at the temperature `t`, one per pressure
+    pub fn row(&self, t: f64) -> Option<&[f64]> {
+        self.temperatures
+            .iter()
+            .position(|&rt| (rt - t).abs() < f64::EPSILON)
+            .map(|idx| self.z[idx].as_slice())
+    }
+
+    /// The Z values of the grid, one row per temperature
+    pub fn rows(&self) -> impl Iterator<Item = (f64, &[f64])> {
+        self.temperatures
+            .iter()
+            .zip(self.z.iter())
+            .map(|(t, z)| (*t, z.as_slice()))
+    }
+
+    /// Populate the grid by evaluating the equation of state for `gas`
+    pub fn fill_z<E: EquationOfState, S: State>(&mut self, gas: &S) {
+        for (t, row) in self.temperatures.iter().zip(self.z.iter_mut()) {
+            let sweep = IsothermSweep::with_pressures(*t, &self.pressures);
+            for (z, (_, sz)) in row.iter_mut().zip(sweep.run::<E, _>(gas)) {
+                *z = sz;
+            }
+        }
+    }
+
+    /// Read a grid from CSV data
+    pub fn from_csv_reader<R: io::Read>(reader: R) -> Result<Self, PtGridError> {
+        let parse = |field: &str, line: usize, column: usize| {
+            let field = field.trim();
+            if field.is_empty() {
+                return Ok(f64::NAN);
+            }
+            field
+                .parse::<f64>()
+                .map_err(|err| PtGridError::Float { err, line, column })
+        };
+
+        let mut lines = io::BufReader::new(reader).lines();
+        let header = lines.next().ok_or(PtGridError::Empty)??;
+        let mut fields = header.split(',');
+        let t_label = fields.next().unwrap_or_default().trim().to_string();
+        let pressures = fields
+            .enumerate()
+            .map(|(idx, field)| parse(field, 1, idx + 2).map(|p| p * 1e5))
+            .collect::<Result<Vec<f64>, _>>()?;
+
+        let mut temperatures = Vec::new();
+        let mut z = Vec::new();
+        for (idx, line) in lines.enumerate() {
+            let line = line?;
+            let lnum = idx + 2;
+            if line.trim().is_empty() {
+                continue;
+            }
+            let fields: Vec<&str> = line.split(',').collect();
+            if fields.len() != pressures.len() + 1 {
+                return Err(PtGridError::RowLength {
+                    line: lnum,
+                    expected: pressures.len() + 1,
+                    found: fields.len(),
+                });
+            }
+            temperatures.push(parse(fields[0], lnum, 1)?);
+            z.push(
+                fields[1..]
+                    .iter()
+                    .enumerate()
+                    .map(|(col, field)| parse(field, lnum, col + 2))
+                    .collect::<Result<Vec<f64>, _>>()?,
+            );
+        }
+
+        Ok(PtGrid {
+            t_label,
+            pressures,
+            temperatures,
+            z,
+        })
+    }
+
+    /// Write the grid as CSV data
+    pub fn to_csv_writer<W: Write>(&self, mut writer: W) -> io::Result<()> {
+        let fmt_value = |v: f64| {
+            if v.is_nan() {
+                "nan".to_string()
+            } else {
+                v.to_string()
+            }
+        };
+
+        write!(writer, "{}", self.t_label)?;
+        for p in &self.pressures {
+            write!(writer, ",{}", fmt_value(p / 1e5))?;
+        }
+        writeln!(writer)?;
+
+        for (t, row) in self.rows() {
+            write!(writer, "{}", fmt_value(t))?;
+            for z in row {
+                write!(writer, ",{}", fmt_value(*z))?;
+            }
+            writeln!(writer)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{PtGrid, PtGridError};
+    use crate::{State, compounds, eos::PengRobinson};
+
+    const CSV: &str = "Temp,1,10,200.5\n\
+                       80,0.9842,nan,0.8316\n\
+                       300,0.9998,0.998,1.0385\n";
+
+    #[test]
+    fn csv_round_trip() {
+        let grid = PtGrid::from_csv_reader(CSV.as_bytes()).unwrap();
+        assert_eq!(grid.pressures(), &[1e5, 10e5, 200.5e5]);
+        assert_eq!(grid.temperatures(), &[80.0, 300.0]);
+        assert!(grid.row(80.0).unwrap()[1].is_nan());
+        assert_eq!(grid.row(300.0).unwrap(), &[0.9998, 0.998, 1.0385]);
+
+        let mut out = Vec::new();
+        grid.to_csv_writer(&mut out).unwrap();
+        assert_eq!(String::from_utf8(out).unwrap(), CSV);
+    }
+
+    #[test]
+    fn csv_errors() {
+        let err = PtGrid::from_csv_reader("T,1,10\n300,1.0,x\n".as_bytes()).unwrap_err();
+        assert!(matches!(err, PtGridError::Float { line: 2, column: 3, .. }));
+
+        let err = PtGrid::from_csv_reader("T,1,10\n300,1.0\n".as_bytes()).unwrap_err();
+        assert!(matches!(err, PtGridError::RowLength { line: 2, expected: 3, found: 2 }));
+
+        let err = PtGrid::from_csv_reader("".as_bytes()).unwrap_err();
+        assert!(matches!(err, PtGridError::Empty));
+    }
+
+    #[test]
+    fn fill_z() {
+        let n2 = compounds::N2;
+        let mut grid = PtGrid::new(&[1e5, 100e5], &[100.0, 300.0]);
+        grid.fill_z::<PengRobinson, _>(&n2);
+        assert_eq!(grid.row(300.0).unwrap()[1], n2.z::<PengRobinson>(100e5, 300.0));
+        assert!(grid.rows().all(|(_, row)| row.iter().all(|z| z.is_finite())));
+    }
+}
@@ -1,13 +1,57 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
 pub mod eos;
+mod dyn_state;
 mod gas;
+#[cfg(feature = "std")]
+mod grid;
 pub mod compounds;
+mod math;
+mod prepared;
+mod saturation;
+mod snapshot;
+mod solver;
+mod sweep;
+mod table;
+mod transport;
+pub mod units;
+mod vle;
 
-use eos::{Eos, EquationOfState};
-pub use gas::{Gas, Mixture, Molecule};
+pub use dyn_state::DynState;
+use eos::{DynEos, Eos, EquationOfState};
+pub use gas::{
+    Comp, Gas, GasParseError, IdealCp, Mixture, MixtureBuilder, MixtureError, Molecule, PseudoCriticalRule, QuantumCorrection,
+    Validity, ValidityRange,
+};
+#[cfg(feature = "std")]
+pub use grid::{PtGrid, PtGridError};
+#[cfg(feature = "std")]
+pub use prepared::ParamsCache;
+pub use prepared::PreparedState;
+pub use snapshot::{GasStateRecord, StateSnapshot};
+pub use solver::{SolverError, SolverOptions};
+pub use sweep::{IsothermSweep, composition_sweep};
+pub use table::ZTable;
+use units::{MolarVolume, Pressure, Temperature};
+#[cfg(all(not(feature = "std"), not(test)))]
+use math::Float;
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
 
 /// Universal gas constant in J/mol.K
 pub const R: f64 = 8.31446262;
 
+/// Pressure of the standard conditions (STP), 1 atm in Pa
+pub const STP_PRESSURE: f64 = 101325.0;
+/// Temperature of the standard conditions (STP), 0 °C in K
+pub const STP_TEMPERATURE: f64 = 273.15;
+/// Pressure of the normal conditions (NTP), 1 atm in Pa
+pub const NTP_PRESSURE: f64 = 101325.0;
+/// Temperature of the normal conditions (NTP), 20 °C in K
+pub const NTP_TEMPERATURE: f64 = 293.15;
+
 /// Pressure, Volume, Temperature state
 #[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
 pub struct Pvt {
@@ -24,6 +68,22 @@ impl Pvt {
     pub fn z(&self) -> f64 {
         self.p * self.v / (R * self.t)
     }
+
+    /// A critical state from the critical temperature `tc` (in K), pressure `pc` (in Pa)
+    /// and the acentric factor `w`, for when the critical volume is unknown.
+    ///
+    /// The critical volume Vc = Zc·R·Tc/Pc is estimated with the correlation
+    /// of Lee and Kesler (1975): Zc = 0.2905 - 0.085ω.
+    /// The estimate is typically within 2% for non-polar fluids,
+    /// but can be off by more than 10% for polar fluids such as water or ammonia.
+    pub fn critical_from_tp(tc: f64, pc: f64, w: f64) -> Pvt {
+        let zc = 0.2905 - 0.085 * w;
+        Pvt {
+            p: pc,
+            v: zc * R * tc / pc,
+            t: tc,
+        }
+    }
 }
 
 /// Pressure, Temperature, compression factor state
@@ -64,21 +124,186 @@ impl From<Pvt> for Ptz {
     }
 }
 
+/// The real roots of the cubic equation of state at given pressure and temperature
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+pub enum PhaseRoots {
+    /// A single positive root: the fluid is in a single phase
+    Single(f64),
+    /// Three positive roots: inside the two-phase region, the middle root being unstable
+    Triple {
+        /// The smallest root, of the liquid phase
+        liquid: f64,
+        /// The middle root, with no physical meaning
+        unstable: f64,
+        /// The largest root, of the vapor phase
+        vapor: f64,
+    },
+}
+
+/// A fluid phase
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Phase {
+    /// The dense phase, of the smallest molar volume
+    Liquid,
+    /// The light phase, of the largest molar volume
+    Vapor,
+}
+
+/// The region of a state, classified by the discriminant of the cubic equation of state
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PhaseRegion {
+    /// A single real root
+    SinglePhase,
+    /// Three distinct real roots
+    TwoPhaseRegion,
+    /// Repeated real roots, such as at the critical point
+    Critical,
+}
+
+/// The reference of absolute enthalpies and entropies:
+/// the ideal gas at the temperature `t` and pressure `p` has the enthalpy `h` and entropy `s`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ReferenceState {
+    /// The reference temperature, in K
+    pub t: f64,
+    /// The reference pressure, in Pa
+    pub p: f64,
+    /// The molar enthalpy of the ideal gas at the reference state, in J/mol
+    pub h: f64,
+    /// The molar entropy of the ideal gas at the reference state, in J/mol.K
+    pub s: f64,
+}
+
+impl ReferenceState {
+    /// Zero enthalpy and entropy for the ideal gas at 25 °C and 1 atm
+    pub const STANDARD: ReferenceState = ReferenceState {
+        t: 298.15,
+        p: 101325.0,
+        h: 0.0,
+        s: 0.0,
+    };
+}
+
+impl Default for ReferenceState {
+    fn default() -> Self {
+        ReferenceState::STANDARD
+    }
+}
+
+/// An error computing the state of a gas
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[non_exhaustive]
+pub enum StateError {
+    /// The equation of state has no positive real root at this pressure and temperature
+    NoRoot { eos: &'static str, p: f64, t: f64 },
+    /// The roots of the equation of state give a molar volume below the covolume b,
+    /// that is a negative free volume
+    BelowCovolume { eos: &'static str, p: f64, t: f64 },
+    /// The pressure is zero or negative, so that the molar volume is not finite
+    NonPositivePressure { p: f64 },
+    /// The computed value is infinite or NaN
+    NonFinite { eos: &'static str, p: f64, t: f64 },
+}
+
+impl core::fmt::Display for StateError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            StateError::NoRoot { eos, p, t } => {
+                write!(f, "{eos}: no positive real root found at P={p} Pa and T={t} K")
+            }
+            StateError::BelowCovolume { eos, p, t } => {
+                write!(f, "{eos}: molar volume below the covolume at P={p} Pa and T={t} K")
+            }
+            StateError::NonPositivePressure { p } => {
+                write!(f, "non-positive pressure P={p} Pa")
+            }
+            StateError::NonFinite { eos, p, t } => {
+                write!(f, "{eos}: non-finite result at P={p} Pa and T={t} K")
+            }
+        }
+    }
+}
+
+impl core::error::Error for StateError {}
+
+/// A warning about the precision of a computed state
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StateWarning {
+    /// The state is close to the critical point, where the roots of the equation coalesce
+    NearCritical,
+}
+
 /// State trait of a gas.
 /// All values here are intensive.
 pub trait State {
     /// The molar mass of the gas, in kg/mol
     fn molar_mass(&self) -> f64;
 
+    /// The specific gas constant R/M, in J/kg.K
+    fn specific_gas_constant(&self) -> f64 {
+        R / self.molar_mass()
+    }
+
+    /// The (pseudo-)critical point of the gas used at the temperature `t`.
+    /// It only depends on `t` for molecules with a quantum correction.
+    ///
+    /// The default implementation inverts the Van der Waals parameters of the gas,
+    /// which is exact for the critical temperature and pressure those parameters were computed from.
+    fn critical_point(&self, t: f64) -> Pvt {
+        let eos::AbParams { a, b } = self.eos_params::<eos::VanDerWaals>(t);
+        Pvt {
+            p: a / (27.0 * b * b),
+            v: 3.0 * b,
+            t: 8.0 * a / (27.0 * R * b),
+        }
+    }
+
+    /// Whether the state is close to the (pseudo-)critical point,
+    /// that is within 5% of Tc and 10% of Pc.
+    ///
+    /// The equation of state is numerically sensitive in this region.
+    fn near_critical(&self, p: f64, t: f64) -> bool {
+        let cs = self.critical_point(t);
+        (t / cs.t - 1.0).abs() <= 0.05 && (p / cs.p - 1.0).abs() <= 0.1
+    }
+
+    /// Whether the temperature `t` is at or above the (pseudo-)critical temperature,
+    /// where the gas can't be liquefied and the saturation properties are not defined
+    fn is_supercritical(&self, t: f64) -> bool {
+        t >= self.critical_point(t).t
+    }
+
     /// Get the parameters for the given equation of state.
     fn eos_params<E: EquationOfState>(&self, t: f64) -> E::Params;
 
+    /// Report the equation of state parameters at given pressure and temperature,
+    /// or `None` if the equation is not expressed with a and b parameters
+    fn eos_params_report<E: EquationOfState>(&self, p: f64, t: f64) -> Option<eos::ParamsReport> {
+        let params = self.eos_params::<E>(t);
+        E::params_report(&params, p, t)
+    }
+
     /// Compute the pressure of the gas for the molar volume and temperature
     fn pressure<E: EquationOfState>(&self, vm: f64, t: f64) -> f64 {
         let params = self.eos_params::<E>(t);
         E::pressure(&params, vm, t)
     }
 
+    /// Compute the second virial coefficient B(T) implied by the equation of state, in m^3/mol
+    fn second_virial<E: EquationOfState>(&self, t: f64) -> f64 {
+        let params = self.eos_params::<E>(t);
+        E::second_virial(&params, t)
+    }
+
+    /// Compute the pressure of the gas along an isochore, that is at the constant molar volume `vm`,
+    /// for each of the `temperatures`
+    fn pressure_isochoric<E: EquationOfState>(&self, vm: f64, temperatures: &[f64]) -> Vec<f64> {
+        temperatures
+            .iter()
+            .map(|&t| self.pressure::<E>(vm, t))
+            .collect()
+    }
+
     /// Compute the compression factor Z such as Z = PV/RT
     ///
     /// Effectively resolves the cubic equation of state as a function of `p` and `t`.
@@ -91,33 +316,591 @@ pub trait State {
     /// This function will panic of no positive real root can be found, which is generally
     /// an indication that the parameters have physical non-sense.
     fn z<E: EquationOfState>(&self, p: f64, t: f64) -> f64 {
-        use roots::Roots;
+        let params = self.eos_params::<E>(t);
+        E::z(&params, p, t).expect("Should have a found a positive real root")
+    }
 
+    /// Compute the compression factor Z like [`State::z`], with `f32` pressure (in Pa) and temperature (in K).
+    ///
+    /// This is an entry point for `f32` data: the computation is done in `f64`,
+    /// so the result is Z rounded to the nearest `f32`.
+    fn z_f32<E: EquationOfState>(&self, p: f32, t: f32) -> f32 {
+        self.z::<E>(f64::from(p), f64::from(t)) as f32
+    }
+
+    /// Compute the compression factor Z such as Z = PV/RT, without panicking.
+    ///
+    /// A warning is returned alongside Z if the state is near the critical point.
+    fn try_z<E: EquationOfState>(
+        &self,
+        p: f64,
+        t: f64,
+    ) -> Result<(f64, Option<StateWarning>), StateError> {
         let params = self.eos_params::<E>(t);
-        let [a3, a2, a1, a0] = E::z_polyn(&params, p, t);
-        let roots = roots::find_roots_cubic(a3, a2, a1, a0);
-        let z = match roots {
-            Roots::No([]) => None,
-            Roots::One([r]) => Some(r),
-            Roots::Two([r1, r2]) => Some(r1.max(r2)),
-            Roots::Three([r1, r2, r3]) => Some(r1.max(r2).max(r3)),
-            _ => unreachable!(),
-        };
-        z.filter(|&z| z > 0.0)
-            .expect("Should have a found a positive real root")
+        let z = E::z(&params, p, t).ok_or_else(|| {
+            if eos::below_covolume::<E>(&params, p, t) {
+                StateError::BelowCovolume { eos: E::NAME, p, t }
+            } else {
+                StateError::NoRoot { eos: E::NAME, p, t }
+            }
+        })?;
+        let warning = self
+            .near_critical(p, t)
+            .then_some(StateWarning::NearCritical);
+        Ok((z, warning))
     }
 
-    /// Compute the molar volume the gas in m^3/mol
+    /// Compute the compression factor Z = PV/RT from the molar volume and temperature.
+    ///
+    /// The pressure is evaluated with the equation of state, so no root finding is involved.
+    fn z_from_vm<E: EquationOfState>(&self, vm: f64, t: f64) -> f64 {
+        self.pressure::<E>(vm, t) * vm / (R * t)
+    }
+
+    /// The coefficients [a3, a2, a1, a0] of the polynomial `a3*Z^3 + a2*Z^2 + a1*Z + a0 = 0`
+    /// that the equation of state solves for `p` and `t`, without solving it
+    fn z_polynomial<E: EquationOfState>(&self, p: f64, t: f64) -> [f64; 4] {
+        let params = self.eos_params::<E>(t);
+        E::z_polyn(&params, p, t)
+    }
+
+    /// Classify the state with the discriminant of the Z polynomial, without solving it.
+    ///
+    /// Equations of state that are not cubic always give [`PhaseRegion::SinglePhase`].
+    fn phase_region<E: EquationOfState>(&self, p: f64, t: f64) -> PhaseRegion {
+        let [a3, a2, a1, a0] = self.z_polynomial::<E>(p, t);
+        if a3.abs() <= 1e-12 * (a2.abs() + a1.abs() + a0.abs()) {
+            return PhaseRegion::SinglePhase;
+        }
+        let (b, c, d) = (a2 / a3, a1 / a3, a0 / a3);
+        let disc = 18.0 * b * c * d - 4.0 * b * b * b * d + b * b * c * c - 4.0 * c * c * c - 27.0 * d * d;
+        if disc.abs() <= 1e-12 {
+            PhaseRegion::Critical
+        } else if disc > 0.0 {
+            PhaseRegion::TwoPhaseRegion
+        } else {
+            PhaseRegion::SinglePhase
+        }
+    }
+
+    /// Compute all the positive roots of the equation of state for `p` and `t`.
+    ///
+    /// # Panics
+    /// This function will panic of no positive real root can be found.
+    fn phase_roots<E: EquationOfState>(&self, p: f64, t: f64) -> PhaseRoots {
+        let params = self.eos_params::<E>(t);
+        match eos::z_roots::<E>(&params, p, t)[..] {
+            [liquid, unstable, vapor] => PhaseRoots::Triple {
+                liquid,
+                unstable,
+                vapor,
+            },
+            [.., z] => PhaseRoots::Single(z),
+            [] => panic!("Should have a found a positive real root"),
+        }
+    }
+
+    /// Compute the molar volume the gas in m^3/mol.
+    ///
+    /// The molar volume tends to infinity as `p` tends to zero, and is infinite at `p = 0`.
+    /// See [`State::try_molar_volume`] to get an error instead.
     fn molar_volume<E: EquationOfState>(&self, p: f64, t: f64) -> f64 {
         let z = self.z::<E>(p, t);
         z * R * t / p
     }
 
-    /// Compute the specific mass of the gas in kg/m^3
+    /// Compute the molar volume of the gas in m^3/mol, without panicking.
+    ///
+    /// Returns [`StateError::NonPositivePressure`] if `p <= 0`,
+    /// and [`StateError::NonFinite`] if the pressure is NaN or the molar volume is not finite.
+    fn try_molar_volume<E: EquationOfState>(&self, p: f64, t: f64) -> Result<f64, StateError> {
+        if p.is_nan() {
+            return Err(StateError::NonFinite { eos: E::NAME, p, t });
+        }
+        if p <= 0.0 {
+            return Err(StateError::NonPositivePressure { p });
+        }
+        let (z, _) = self.try_z::<E>(p, t)?;
+        let vm = z * R * t / p;
+        if !vm.is_finite() {
+            return Err(StateError::NonFinite { eos: E::NAME, p, t });
+        }
+        Ok(vm)
+    }
+
+    /// Compute the molar volume of the requested `phase` in m^3/mol,
+    /// by solving P(Vm) = `p` with a bracketed root finding instead of the Z polynomial.
+    ///
+    /// The vapor volume is searched downward from 10·RT/P, and the liquid volume upward
+    /// from the covolume b. In the single phase region, both give the same volume.
+    /// This is slower than [`State::molar_volume`] but useful to validate it.
+    /// Returns `None` if no volume can be bracketed.
+    fn molar_volume_bracketed<E: EquationOfState>(&self, p: f64, t: f64, phase: Phase) -> Option<f64> {
+        self.molar_volume_bracketed_with_options::<E>(p, t, phase, &SolverOptions::default())
+            .ok()
+    }
+
+    /// Compute the molar volume of `phase` like [`State::molar_volume_bracketed`],
+    /// with the convergence control of `options`
+    fn molar_volume_bracketed_with_options<E: EquationOfState>(
+        &self,
+        p: f64,
+        t: f64,
+        phase: Phase,
+        options: &SolverOptions,
+    ) -> Result<f64, SolverError> {
+        const STEPS: usize = 2000;
+        const RATIO: f64 = 1.02;
+
+        let params = self.eos_params::<E>(t);
+        let f = |vm: f64| E::pressure(&params, vm, t) - p;
+        let b = E::params_report(&params, p, t).map_or(0.0, |r| r.b);
+
+        let (mut v0, ratio) = match phase {
+            Phase::Vapor => (10.0 * R * t / p, 1.0 / RATIO),
+            Phase::Liquid => (b + 1e-9 * R * t / p, RATIO),
+        };
+        let f0 = f(v0);
+        for _ in 0..STEPS {
+            let v1 = b + (v0 - b) * ratio;
+            if f(v1).signum() != f0.signum() {
+                return solver::find_root_brent(v0, v1, f, v1, options);
+            }
+            v0 = v1;
+        }
+        Err(SolverError::NoSolution)
+    }
+
+    /// Compute the specific mass of the gas in kg/m^3.
+    ///
+    /// The specific mass tends to zero as `p` tends to zero, and is zero at `p = 0`.
     fn specific_mass<E: EquationOfState>(&self, p: f64, t: f64) -> f64 {
         let z = self.z::<E>(p, t);
         self.molar_mass() * p / (z * R * t)
     }
+
+    /// Compute the specific mass of the gas in kg/m^3, without panicking.
+    ///
+    /// Unlike [`State::specific_mass`], a zero or negative pressure is rejected
+    /// with [`StateError::NonPositivePressure`], consistently with [`State::try_molar_volume`].
+    fn try_specific_mass<E: EquationOfState>(&self, p: f64, t: f64) -> Result<f64, StateError> {
+        let vm = self.try_molar_volume::<E>(p, t)?;
+        Ok(self.molar_mass() / vm)
+    }
+
+    /// Compute the density of the gas in kg/m^3 from pressure and temperature.
+    ///
+    /// Same as [`State::specific_mass`], and inverse of [`State::pressure_from_density`].
+    fn density<E: EquationOfState>(&self, p: f64, t: f64) -> f64 {
+        self.specific_mass::<E>(p, t)
+    }
+
+    /// Compute the pressure of the gas in Pa from density in kg/m^3 and temperature.
+    ///
+    /// Inverse of [`State::density`]. No root finding is involved.
+    fn pressure_from_density<E: EquationOfState>(&self, rho: f64, t: f64) -> f64 {
+        self.pressure::<E>(self.molar_mass() / rho, t)
+    }
+
+    /// Compute the specific mass of the gas in kg/m^3 at standard conditions (STP),
+    /// that is 0 °C and 1 atm ([`STP_TEMPERATURE`] and [`STP_PRESSURE`])
+    fn standard_density<E: EquationOfState>(&self) -> f64 {
+        self.specific_mass::<E>(STP_PRESSURE, STP_TEMPERATURE)
+    }
+
+    /// Compute the specific mass of the gas in kg/m^3 at normal conditions (NTP),
+    /// that is 20 °C and 1 atm ([`NTP_TEMPERATURE`] and [`NTP_PRESSURE`])
+    fn normal_density<E: EquationOfState>(&self) -> f64 {
+        self.specific_mass::<E>(NTP_PRESSURE, NTP_TEMPERATURE)
+    }
+
+    /// Compute the specific volume of the gas in m^3/kg
+    fn specific_volume<E: EquationOfState>(&self, p: f64, t: f64) -> f64 {
+        let z = self.z::<E>(p, t);
+        z * self.specific_gas_constant() * t / p
+    }
+
+    /// Resolve the complete [`Pvt`] state from the pressure and temperature
+    fn pvt_from_pt<E: EquationOfState>(&self, p: f64, t: f64) -> Pvt {
+        self.ptz_from_pt::<E>(p, t).into()
+    }
+
+    /// Resolve the complete [`Ptz`] state from the pressure and temperature
+    fn ptz_from_pt<E: EquationOfState>(&self, p: f64, t: f64) -> Ptz {
+        let z = self.z::<E>(p, t);
+        Ptz { p, t, z }
+    }
+
+    /// Compute the fugacity coefficient φ of the gas, such as the fugacity is f = φP
+    fn fugacity_coefficient<E: EquationOfState>(&self, p: f64, t: f64) -> f64 {
+        let params = self.eos_params::<E>(t);
+        let z = E::z(&params, p, t).expect("Should have a found a positive real root");
+        ln_fugacity_coefficient::<E>(&params, z, p, t).exp()
+    }
+
+    /// Compute the enthalpy departure H - H_ig from the ideal gas
+    /// at the same pressure and temperature, in J/mol
+    fn enthalpy_departure<E: EquationOfState>(&self, p: f64, t: f64) -> f64 {
+        let z = self.z::<E>(p, t);
+        enthalpy_departure::<E, _>(self, z * R * t / p, z, t)
+    }
+
+    /// Compute the entropy departure S - S_ig from the ideal gas
+    /// at the same pressure and temperature, in J/mol.K
+    fn entropy_departure<E: EquationOfState>(&self, p: f64, t: f64) -> f64 {
+        let z = self.z::<E>(p, t);
+        entropy_departure::<E, _>(self, z * R * t / p, z, t)
+    }
+
+    /// Compute the reversible work to compress isothermally one mole of gas
+    /// from the pressure `p1` to `p2` at the temperature `t`, in J/mol.
+    ///
+    /// This is the integral of Vm·dP, computed by quadrature over ln(P).
+    fn isothermal_work<E: EquationOfState>(&self, p1: f64, p2: f64, t: f64) -> f64 {
+        let params = self.eos_params::<E>(t);
+        // Vm·dP = Z·RT·d(ln P)
+        let z = |ln_p: f64| {
+            E::z(&params, ln_p.exp(), t).expect("Should have a found a positive real root")
+        };
+        R * t * eos::integrate(z, p1.ln(), p2.ln())
+    }
+
+    /// Compute the derivative of the pressure with respect to the molar volume (∂P/∂Vm)_T, in Pa.mol/m^3
+    fn dpdv<E: EquationOfState>(&self, vm: f64, t: f64) -> f64 {
+        let params = self.eos_params::<E>(t);
+        E::dpdv(&params, vm, t)
+    }
+
+    /// Compute the derivative of the pressure with respect to the temperature (∂P/∂T)_Vm, in Pa/K
+    ///
+    /// As the equation of state parameters depend on the temperature,
+    /// this is computed with a central finite difference.
+    fn dpdt<E: EquationOfState>(&self, vm: f64, t: f64) -> f64 {
+        let h = t * 1e-6;
+        (self.pressure::<E>(vm, t + h) - self.pressure::<E>(vm, t - h)) / (2.0 * h)
+    }
+
+    /// Compute the isothermal compressibility κT = -(1/V)(∂V/∂P)_T, in 1/Pa
+    fn isothermal_compressibility<E: EquationOfState>(&self, p: f64, t: f64) -> f64 {
+        let vm = self.molar_volume::<E>(p, t);
+        -1.0 / (vm * self.dpdv::<E>(vm, t))
+    }
+
+    /// Compute the isobaric thermal expansion coefficient β = (1/V)(∂V/∂T)_P, in 1/K
+    fn thermal_expansion<E: EquationOfState>(&self, p: f64, t: f64) -> f64 {
+        let vm = self.molar_volume::<E>(p, t);
+        -self.dpdt::<E>(vm, t) / (vm * self.dpdv::<E>(vm, t))
+    }
+
+    /// Compute the specific mass of the gas in kg/m^3 and its 1-sigma uncertainty,
+    /// from the 1-sigma uncertainties `dp` of the pressure (Pa) and `dt` of the temperature (K).
+    ///
+    /// This is a first-order propagation of independent Gaussian uncertainties,
+    /// with ∂ρ/∂P = ρ·κT and ∂ρ/∂T = -ρ·β.
+    fn specific_mass_with_uncertainty<E: EquationOfState>(&self, p: f64, dp: f64, t: f64, dt: f64) -> (f64, f64) {
+        let rho = self.specific_mass::<E>(p, t);
+        let drho_p = rho * self.isothermal_compressibility::<E>(p, t) * dp;
+        let drho_t = rho * self.thermal_expansion::<E>(p, t) * dt;
+        (rho, drho_p.hypot(drho_t))
+    }
+
+    /// The isobaric heat capacity of the ideal gas at the temperature `t`, in J/mol.K,
+    /// or `None` if it is not known
+    fn ideal_cp(&self, t: f64) -> Option<f64> {
+        let _ = t;
+        None
+    }
+
+    /// Compute the residual isochoric heat capacity Cv - Cv_ig, in J/mol.K
+    ///
+    /// This is -T·∂²(A - A_ig)/∂T² at constant molar volume, computed with a central finite difference.
+    fn residual_cv<E: EquationOfState>(&self, vm: f64, t: f64) -> f64 {
+        let ar = |t: f64| R * t * E::residual_helmholtz(&self.eos_params::<E>(t), vm, t);
+        let h = t * 1e-3;
+        -t * (ar(t + h) - 2.0 * ar(t) + ar(t - h)) / (h * h)
+    }
+
+    /// Compute the molar isobaric heat capacity of the gas, in J/mol.K,
+    /// or `None` if the ideal gas heat capacity is not known
+    fn isobaric_heat_capacity<E: EquationOfState>(&self, p: f64, t: f64) -> Option<f64> {
+        let vm = self.molar_volume::<E>(p, t);
+        isobaric_heat_capacity::<E, _>(self, vm, t)
+    }
+
+    /// Compute the heat capacity ratio γ = Cp/Cv of the gas,
+    /// or `None` if the ideal gas heat capacity is not known.
+    ///
+    /// Cv is the ideal gas heat capacity plus [`State::residual_cv`],
+    /// and Cp is obtained from Cv with Cp - Cv = -T·(∂P/∂T)²/(∂P/∂V).
+    fn heat_capacity_ratio<E: EquationOfState>(&self, p: f64, t: f64) -> Option<f64> {
+        let vm = self.molar_volume::<E>(p, t);
+        let cv = self.ideal_cp(t)? - R + self.residual_cv::<E>(vm, t);
+        let cp = isobaric_heat_capacity::<E, _>(self, vm, t)?;
+        Some(cp / cv)
+    }
+
+    /// Compute the molar enthalpy of the gas relative to `reference`, in J/mol,
+    /// or `None` if the ideal gas heat capacity is not known.
+    ///
+    /// This is the enthalpy of the ideal gas, integrated from the reference temperature,
+    /// plus the enthalpy departure.
+    fn enthalpy<E: EquationOfState>(&self, p: f64, t: f64, reference: &ReferenceState) -> Option<f64> {
+        self.ideal_cp(t)?;
+        let cp = |t: f64| self.ideal_cp(t).unwrap_or(f64::NAN);
+        Some(reference.h + eos::integrate(cp, reference.t, t) + self.enthalpy_departure::<E>(p, t))
+    }
+
+    /// Compute the molar entropy of the gas relative to `reference`, in J/mol.K,
+    /// or `None` if the ideal gas heat capacity is not known.
+    ///
+    /// This is the entropy of the ideal gas, integrated from the reference temperature
+    /// and pressure, plus the entropy departure.
+    fn entropy<E: EquationOfState>(&self, p: f64, t: f64, reference: &ReferenceState) -> Option<f64> {
+        self.ideal_cp(t)?;
+        let cp_t = |t: f64| self.ideal_cp(t).unwrap_or(f64::NAN) / t;
+        let s_ig = eos::integrate(cp_t, reference.t, t) - R * (p / reference.p).ln();
+        Some(reference.s + s_ig + self.entropy_departure::<E>(p, t))
+    }
+
+    /// Compute the temperature reached after compressing (or expanding) isentropically the gas
+    /// from the pressure `p1` and temperature `t1` to the pressure `p2`, in K.
+    ///
+    /// The entropy of the ideal gas and the entropy departure are conserved.
+    /// Returns `None` if the ideal gas heat capacity is not known or if no temperature is found.
+    fn isentropic_temperature<E: EquationOfState>(&self, p1: f64, t1: f64, p2: f64) -> Option<f64> {
+        self.isentropic_temperature_with_options::<E>(p1, t1, p2, &SolverOptions::default())
+            .ok()
+    }
+
+    /// Compute the temperature reached after an isentropic compression like [`State::isentropic_temperature`],
+    /// with the convergence control of `options`.
+    /// Returns [`SolverError::NoSolution`] if the ideal gas heat capacity is not known.
+    fn isentropic_temperature_with_options<E: EquationOfState>(
+        &self,
+        p1: f64,
+        t1: f64,
+        p2: f64,
+        options: &SolverOptions,
+    ) -> Result<f64, SolverError> {
+        self.ideal_cp(t1).ok_or(SolverError::NoSolution)?;
+        let s1 = self.entropy_departure::<E>(p1, t1);
+        // entropy change from (p1, t1) to (p2, t2)
+        let ds = |t2: f64| {
+            let cp_t = |t: f64| self.ideal_cp(t).unwrap_or(f64::NAN) / t;
+            eos::integrate(cp_t, t1, t2) - R * (p2 / p1).ln() + self.entropy_departure::<E>(p2, t2) - s1
+        };
+
+        // the entropy increases with the temperature
+        let ratio = if p2 > p1 { 1.2 } else { 1.0 / 1.2 };
+        let mut t0 = t1;
+        for _ in 0..50 {
+            let t = t0 * ratio;
+            if (ds(t) > 0.0) == (p2 > p1) {
+                return solver::find_root_brent(t0, t, ds, t, options);
+            }
+            t0 = t;
+        }
+        Err(SolverError::NoSolution)
+    }
+
+    /// Compute the Joule-Thomson coefficient μJT = (∂T/∂P)_H, in K/Pa,
+    /// or `None` if the ideal gas heat capacity is not known.
+    ///
+    /// The gas cools down on expansion when μJT is positive.
+    fn joule_thomson<E: EquationOfState>(&self, p: f64, t: f64) -> Option<f64> {
+        let cp = self.isobaric_heat_capacity::<E>(p, t)?;
+        let vm = self.molar_volume::<E>(p, t);
+        Some(vm * (t * self.thermal_expansion::<E>(p, t) - 1.0) / cp)
+    }
+
+    /// Compute the Joule-Thomson inversion curve, as (P, T) pairs where μJT vanishes.
+    ///
+    /// For each pressure, the upper inversion temperature is searched downward from 20·Tc to Tc.
+    /// Pressures without inversion temperature in this range are omitted.
+    /// As the heat capacity doesn't change the sign of μJT, it is not needed here.
+    fn inversion_curve<E: EquationOfState>(&self, pressures: &[f64]) -> Vec<(f64, f64)> {
+        self.inversion_curve_with_options::<E>(pressures, &SolverOptions::default())
+    }
+
+    /// Compute the Joule-Thomson inversion curve like [`State::inversion_curve`],
+    /// with the convergence control of `options`
+    fn inversion_curve_with_options<E: EquationOfState>(
+        &self,
+        pressures: &[f64],
+        options: &SolverOptions,
+    ) -> Vec<(f64, f64)> {
+        const STEPS: i32 = 200;
+
+        let tc = self.critical_point(STP_TEMPERATURE).t;
+        let ratio = 20f64.powf(1.0 / STEPS as f64);
+
+        pressures
+            .iter()
+            .filter_map(|&p| {
+                // numerator of μJT, of the same sign
+                let f = |t: f64| t * self.thermal_expansion::<E>(p, t) - 1.0;
+                let mut t_hi = 20.0 * tc;
+                if f(t_hi) >= 0.0 {
+                    return None;
+                }
+                for _ in 0..STEPS {
+                    let t_lo = t_hi / ratio;
+                    if f(t_lo) >= 0.0 {
+                        return solver::find_root_brent(t_lo, t_hi, f, t_hi, options)
+                            .ok()
+                            .map(|t| (p, t));
+                    }
+                    t_hi = t_lo;
+                }
+                None
+            })
+            .collect()
+    }
+
+    /// Resolve the state at given pressure and temperature in a single [`StateSnapshot`],
+    /// solving the equation of state only once
+    fn snapshot<E: EquationOfState>(&self, p: f64, t: f64) -> StateSnapshot {
+        let z = self.z::<E>(p, t);
+        let molar_volume = z * R * t / p;
+        StateSnapshot {
+            p,
+            t,
+            z,
+            molar_volume,
+            specific_mass: self.molar_mass() / molar_volume,
+            enthalpy_departure: enthalpy_departure::<E, _>(self, molar_volume, z, t),
+            entropy_departure: entropy_departure::<E, _>(self, molar_volume, z, t),
+            cp: isobaric_heat_capacity::<E, _>(self, molar_volume, t),
+        }
+    }
+
+    /// Compute the pressure of the gas for the unit-typed molar volume and temperature
+    fn pressure_at<E: EquationOfState>(&self, vm: MolarVolume, t: Temperature) -> Pressure {
+        Pressure::pascal(self.pressure::<E>(vm.to_cubic_meter_per_mol(), t.to_kelvin()))
+    }
+
+    /// Compute the compression factor Z for the unit-typed pressure and temperature
+    fn z_at<E: EquationOfState>(&self, p: Pressure, t: Temperature) -> f64 {
+        self.z::<E>(p.to_pascal(), t.to_kelvin())
+    }
+
+    /// Compute the molar volume of the gas for the unit-typed pressure and temperature
+    fn molar_volume_at<E: EquationOfState>(&self, p: Pressure, t: Temperature) -> MolarVolume {
+        MolarVolume::cubic_meter_per_mol(self.molar_volume::<E>(p.to_pascal(), t.to_kelvin()))
+    }
+
+    /// Compute the specific mass of the gas in kg/m^3 for the unit-typed pressure and temperature
+    fn specific_mass_at<E: EquationOfState>(&self, p: Pressure, t: Temperature) -> f64 {
+        self.specific_mass::<E>(p.to_pascal(), t.to_kelvin())
+    }
+
+    /// Compute the compression factor Z over a grid of pressures and temperatures.
+    ///
+    /// Returns one row per temperature, with one Z value per pressure.
+    fn z_grid<E: EquationOfState>(&self, pressures: &[f64], temperatures: &[f64]) -> Vec<Vec<f64>>
+    where
+        Self: Sized,
+    {
+        temperatures
+            .iter()
+            .map(|&t| {
+                let state = self.prepare::<E>(t);
+                pressures.iter().map(|&p| state.z(p)).collect()
+            })
+            .collect()
+    }
+
+    /// Compute the compression factor Z over a grid of pressures and temperatures,
+    /// with the temperature rows computed in parallel.
+    ///
+    /// The result is identical to [`State::z_grid`].
+    #[cfg(feature = "rayon")]
+    fn z_grid_par<E: EquationOfState>(
+        &self,
+        pressures: &[f64],
+        temperatures: &[f64],
+    ) -> Vec<Vec<f64>>
+    where
+        Self: Sized + Sync,
+    {
+        use rayon::prelude::*;
+
+        temperatures
+            .par_iter()
+            .map(|&t| {
+                let state = self.prepare::<E>(t);
+                pressures.iter().map(|&p| state.z(p)).collect()
+            })
+            .collect()
+    }
+
+    /// Prepare the state of the gas at a fixed temperature.
+    ///
+    /// The equation of state parameters are computed once and reused for
+    /// every subsequent evaluation of the returned [`PreparedState`].
+    /// This is useful when evaluating many pressures at a single temperature.
+    fn prepare<E: EquationOfState>(&self, t: f64) -> PreparedState<'_, Self, E>
+    where
+        Self: Sized,
+    {
+        PreparedState::new(self, t)
+    }
+
+    /// Lazily compute the compression factor Z for each of `pressures` (in Pa) at the temperature `t` (in K).
+    ///
+    /// The equation of state parameters are computed once, like with [`State::prepare`].
+    ///
+    /// # Panics
+    /// The iterator will panic if no positive real root can be found for a pressure, like [`State::z`].
+    fn z_iter<'a, E: EquationOfState + 'a>(
+        &'a self,
+        pressures: impl Iterator<Item = f64> + 'a,
+        t: f64,
+    ) -> impl Iterator<Item = f64> + 'a
+    where
+        Self: Sized,
+    {
+        let state = self.prepare::<E>(t);
+        pressures.map(move |p| state.z(p))
+    }
+}
+
+/// The logarithm of the fugacity coefficient for the compression factor `z`
+pub(crate) fn ln_fugacity_coefficient<E: EquationOfState>(
+    params: &E::Params,
+    z: f64,
+    p: f64,
+    t: f64,
+) -> f64 {
+    let vm = z * R * t / p;
+    E::residual_helmholtz(params, vm, t) + z - 1.0 - z.ln()
+}
+
+/// The derivative of the residual Helmholtz energy over RT with respect to the temperature,
+/// at constant molar volume
+fn residual_helmholtz_dt<E: EquationOfState, S: State + ?Sized>(state: &S, vm: f64, t: f64) -> f64 {
+    let ar = |t: f64| E::residual_helmholtz(&state.eos_params::<E>(t), vm, t);
+    let h = t * 1e-6;
+    (ar(t + h) - ar(t - h)) / (2.0 * h)
+}
+
+/// The enthalpy departure at the molar volume `vm` and compression factor `z`, in J/mol
+fn enthalpy_departure<E: EquationOfState, S: State + ?Sized>(state: &S, vm: f64, z: f64, t: f64) -> f64 {
+    R * t * (z - 1.0 - t * residual_helmholtz_dt::<E, _>(state, vm, t))
+}
+
+/// The entropy departure at the molar volume `vm` and compression factor `z`, in J/mol.K
+fn entropy_departure<E: EquationOfState, S: State + ?Sized>(state: &S, vm: f64, z: f64, t: f64) -> f64 {
+    let ar = E::residual_helmholtz(&state.eos_params::<E>(t), vm, t);
+    R * (z.ln() - ar - t * residual_helmholtz_dt::<E, _>(state, vm, t))
+}
+
+/// The isobaric heat capacity at the molar volume `vm`, in J/mol.K
+fn isobaric_heat_capacity<E: EquationOfState, S: State + ?Sized>(state: &S, vm: f64, t: f64) -> Option<f64> {
+    let cv_ig = state.ideal_cp(t)? - R;
+    let dpdt = state.dpdt::<E>(vm, t);
+    let cv = cv_ig + state.residual_cv::<E>(vm, t);
+    Some(cv - t * dpdt * dpdt / state.dpdv::<E>(vm, t))
 }
 
 /// An helper trait to compute extensive state
@@ -140,6 +923,13 @@ pub trait ExtensiveState: State {
         n * z * R * t / p
     }
 
+    /// Compute the volume of the gas for given pressure, mols and temperature, without panicking.
+    ///
+    /// Returns [`StateError::NonPositivePressure`] if `p <= 0`, where the volume would be infinite.
+    fn try_volume<E: EquationOfState>(&self, p: f64, n: f64, t: f64) -> Result<f64, StateError> {
+        Ok(n * self.try_molar_volume::<E>(p, t)?)
+    }
+
     /// Compute the mass of the gas for given pressure, volume and temperature.
     fn mass<E: EquationOfState>(&self, p: f64, v: f64, t: f64) -> f64 {
         let n = self.mols::<E>(p, v, t);
@@ -155,10 +945,14 @@ pub trait StateEos: State {
         match eos {
             Eos::IdealGas => self.pressure::<eos::IdealGas>(vm, t),
             Eos::VanDerWaals => self.pressure::<eos::VanDerWaals>(vm, t),
+            Eos::Clausius => self.pressure::<eos::Clausius>(vm, t),
+            Eos::Berthelot => self.pressure::<eos::Berthelot>(vm, t),
             Eos::RedlichKwong => self.pressure::<eos::RedlichKwong>(vm, t),
             Eos::SoaveRedlichKwong => self.pressure::<eos::SoaveRedlichKwong>(vm, t),
             Eos::PengRobinson => self.pressure::<eos::PengRobinson>(vm, t),
+            Eos::PengRobinson1976 => self.pressure::<eos::PengRobinson1976>(vm, t),
             Eos::PatelTejaValderrama => self.pressure::<eos::PatelTejaValderrama>(vm, t),
+            Eos::LeeKesler => self.pressure::<eos::LeeKesler>(vm, t),
         }
     }
 
@@ -177,13 +971,119 @@ pub trait StateEos: State {
         match eos {
             Eos::IdealGas => self.z::<eos::IdealGas>(p, t),
             Eos::VanDerWaals => self.z::<eos::VanDerWaals>(p, t),
+            Eos::Clausius => self.z::<eos::Clausius>(p, t),
+            Eos::Berthelot => self.z::<eos::Berthelot>(p, t),
             Eos::RedlichKwong => self.z::<eos::RedlichKwong>(p, t),
             Eos::SoaveRedlichKwong => self.z::<eos::SoaveRedlichKwong>(p, t),
             Eos::PengRobinson => self.z::<eos::PengRobinson>(p, t),
+            Eos::PengRobinson1976 => self.z::<eos::PengRobinson1976>(p, t),
             Eos::PatelTejaValderrama => self.z::<eos::PatelTejaValderrama>(p, t),
+            Eos::LeeKesler => self.z::<eos::LeeKesler>(p, t),
+        }
+    }
+
+    /// Compute the compression factor Z with every equation of state of [`Eos::all`].
+    ///
+    /// # Panics
+    /// This function will panic if one of the equations has no positive real root, see [`StateEos::z_eos`].
+    fn z_all_eos(&self, p: f64, t: f64) -> Vec<(Eos, f64)> {
+        Eos::all().iter().map(|&eos| (eos, self.z_eos(eos, p, t))).collect()
+    }
+
+    /// Compute the compression factor Z such as Z = PV/RT, without panicking.
+    /// See [`State::try_z`].
+    fn try_z_eos(&self, eos: Eos, p: f64, t: f64) -> Result<(f64, Option<StateWarning>), StateError> {
+        match eos {
+            Eos::IdealGas => self.try_z::<eos::IdealGas>(p, t),
+            Eos::VanDerWaals => self.try_z::<eos::VanDerWaals>(p, t),
+            Eos::Clausius => self.try_z::<eos::Clausius>(p, t),
+            Eos::Berthelot => self.try_z::<eos::Berthelot>(p, t),
+            Eos::RedlichKwong => self.try_z::<eos::RedlichKwong>(p, t),
+            Eos::SoaveRedlichKwong => self.try_z::<eos::SoaveRedlichKwong>(p, t),
+            Eos::PengRobinson => self.try_z::<eos::PengRobinson>(p, t),
+            Eos::PengRobinson1976 => self.try_z::<eos::PengRobinson1976>(p, t),
+            Eos::PatelTejaValderrama => self.try_z::<eos::PatelTejaValderrama>(p, t),
+            Eos::LeeKesler => self.try_z::<eos::LeeKesler>(p, t),
         }
     }
 
+    /// Compute all the positive roots of the equation of state for `p` and `t`,
+    /// with a runtime defined equation of state
+    ///
+    /// # Panics
+    /// This function will panic of no positive real root can be found.
+    fn phase_roots_eos(&self, eos: Eos, p: f64, t: f64) -> PhaseRoots {
+        match eos {
+            Eos::IdealGas => self.phase_roots::<eos::IdealGas>(p, t),
+            Eos::VanDerWaals => self.phase_roots::<eos::VanDerWaals>(p, t),
+            Eos::Clausius => self.phase_roots::<eos::Clausius>(p, t),
+            Eos::Berthelot => self.phase_roots::<eos::Berthelot>(p, t),
+            Eos::RedlichKwong => self.phase_roots::<eos::RedlichKwong>(p, t),
+            Eos::SoaveRedlichKwong => self.phase_roots::<eos::SoaveRedlichKwong>(p, t),
+            Eos::PengRobinson => self.phase_roots::<eos::PengRobinson>(p, t),
+            Eos::PengRobinson1976 => self.phase_roots::<eos::PengRobinson1976>(p, t),
+            Eos::PatelTejaValderrama => self.phase_roots::<eos::PatelTejaValderrama>(p, t),
+            Eos::LeeKesler => self.phase_roots::<eos::LeeKesler>(p, t),
+        }
+    }
+
+    /// Resolve the state at given pressure and temperature in a single [`StateSnapshot`],
+    /// with a runtime defined equation of state. See [`State::snapshot`].
+    fn snapshot_eos(&self, eos: Eos, p: f64, t: f64) -> StateSnapshot {
+        match eos {
+            Eos::IdealGas => self.snapshot::<eos::IdealGas>(p, t),
+            Eos::VanDerWaals => self.snapshot::<eos::VanDerWaals>(p, t),
+            Eos::Clausius => self.snapshot::<eos::Clausius>(p, t),
+            Eos::Berthelot => self.snapshot::<eos::Berthelot>(p, t),
+            Eos::RedlichKwong => self.snapshot::<eos::RedlichKwong>(p, t),
+            Eos::SoaveRedlichKwong => self.snapshot::<eos::SoaveRedlichKwong>(p, t),
+            Eos::PengRobinson => self.snapshot::<eos::PengRobinson>(p, t),
+            Eos::PengRobinson1976 => self.snapshot::<eos::PengRobinson1976>(p, t),
+            Eos::PatelTejaValderrama => self.snapshot::<eos::PatelTejaValderrama>(p, t),
+            Eos::LeeKesler => self.snapshot::<eos::LeeKesler>(p, t),
+        }
+    }
+
+    /// Get the parameters of a runtime defined equation of state
+    fn dyn_params(&self, eos: &dyn DynEos, t: f64) -> eos::AbParams {
+        use eos::MixingRules;
+
+        let eos::Components(comps) = self.eos_params::<eos::CollectComponents>(t);
+        let params: Vec<(f64, eos::AbParams)> = comps
+            .iter()
+            .map(|(f, cs, w)| (*f, eos.params(cs, *w, t)))
+            .collect();
+        eos::AbParams::mix(&params)
+    }
+
+    /// Compute the pressure of the gas with a runtime defined equation of state
+    fn pressure_dyn(&self, eos: &dyn DynEos, vm: f64, t: f64) -> f64 {
+        let params = self.dyn_params(eos, t);
+        eos.pressure(&params, vm, t)
+    }
+
+    /// Compute the compression factor Z with a runtime defined equation of state
+    ///
+    /// # Panics
+    /// This function will panic of no positive real root can be found.
+    fn z_dyn(&self, eos: &dyn DynEos, p: f64, t: f64) -> f64 {
+        let params = self.dyn_params(eos, t);
+        eos.z(&params, p, t)
+            .expect("Should have a found a positive real root")
+    }
+
+    /// Compute the molar volume the gas in m^3/mol with a runtime defined equation of state
+    fn molar_volume_dyn(&self, eos: &dyn DynEos, p: f64, t: f64) -> f64 {
+        let z = self.z_dyn(eos, p, t);
+        z * R * t / p
+    }
+
+    /// Compute the specific mass of the gas in kg/m^3 with a runtime defined equation of state
+    fn specific_mass_dyn(&self, eos: &dyn DynEos, p: f64, t: f64) -> f64 {
+        let z = self.z_dyn(eos, p, t);
+        self.molar_mass() * p / (z * R * t)
+    }
+
     /// Compute the molar volume the gas in m^3/mol
     fn molar_volume_eos(&self, eos: Eos, p: f64, t: f64) -> f64 {
         let z = self.z_eos(eos, p, t);
@@ -228,12 +1128,21 @@ pub trait ExtensiveStateEos: StateEos {
 }
 
 impl State for Molecule {
+    fn critical_point(&self, t: f64) -> Pvt {
+        self.effective_critical_state(t).0
+    }
+
     fn eos_params<E: EquationOfState>(&self, t: f64) -> E::Params {
-        E::params(&self.critical_state, self.w, t)
+        let (cs, w) = self.effective_critical_state(t);
+        E::params_with_alpha(&cs, w, &self.alpha, t)
+    }
+
+    fn ideal_cp(&self, t: f64) -> Option<f64> {
+        self.cp.map(|cp| cp.cp(t))
     }
 
     fn molar_mass(&self) -> f64 {
-        self.m
+        Molecule::molar_mass(self)
     }
 }
 
@@ -242,20 +1151,26 @@ impl StateEos for Molecule {}
 impl ExtensiveStateEos for Molecule {}
 
 impl State for Mixture {
+    fn critical_point(&self, _t: f64) -> Pvt {
+        self.pseudo_critical()
+    }
+
     fn eos_params<E: EquationOfState>(&self, t: f64) -> E::Params {
         use eos::MixingRules;
 
         let params = self.comps
             .iter()
-            .map(|(f, m)| (*f, E::params(&m.critical_state, m.w, t)));
+            .map(|(f, m)| (*f, m.eos_params::<E>(t)));
 
         E::Params::mix(params)
     }
 
+    fn ideal_cp(&self, t: f64) -> Option<f64> {
+        self.comps.iter().map(|(f, m)| m.ideal_cp(t).map(|cp| f * cp)).sum()
+    }
+
     fn molar_mass(&self) -> f64 {
-        self.comps
-            .iter()
-            .fold(0.0, |s, (f, m)| s + f * m.m)
+        Mixture::molar_mass(self)
     }
 }
 
@@ -264,6 +1179,13 @@ impl StateEos for Mixture {}
 impl ExtensiveStateEos for Mixture {}
 
 impl State for Gas {
+    fn critical_point(&self, t: f64) -> Pvt {
+        match self {
+            Gas::Molecule(m) => m.critical_point(t),
+            Gas::Mixture(m) => m.critical_point(t),
+        }
+    }
+
     fn eos_params<E: EquationOfState>(&self, t: f64) -> E::Params {
         match self {
             Gas::Molecule(m) => m.eos_params::<E>(t),
@@ -271,21 +1193,25 @@ impl State for Gas {
         }
     }
 
-    fn molar_mass(&self) -> f64 {
+    fn ideal_cp(&self, t: f64) -> Option<f64> {
         match self {
-            Gas::Molecule(props) => props.molar_mass(),
-            Gas::Mixture(mix) => mix.molar_mass(),
+            Gas::Molecule(m) => m.ideal_cp(t),
+            Gas::Mixture(m) => m.ideal_cp(t),
         }
     }
+
+    fn molar_mass(&self) -> f64 {
+        Gas::molar_mass(self)
+    }
 }
 
 impl ExtensiveState for Gas {}
 impl StateEos for Gas {}
 impl ExtensiveStateEos for Gas {}
 
-#[cfg(test)]
+#[cfg(all(test, feature = "std"))]
 mod tests {
-    use super::{State};
+    use super::{Molecule, PhaseRoots, Pvt, R, State};
     use crate::{eos, compounds};
     use float_eq::assert_float_eq;
 
@@ -302,10 +1228,650 @@ mod tests {
         let mass = h2.specific_mass::<E>(p, t);
         assert_float_eq!(mass, h2_storage_mass, r2nd <= 0.05);
 
+        // the quantum correction fades at room temperature, but still improves the prediction
+        let h2q = h2.with_quantum_correction(compounds::H2_QUANTUM_CORRECTION);
+        let mass_q = h2q.specific_mass::<E>(p, t);
+        assert_float_eq!(mass_q, h2_storage_mass, r2nd <= 0.048);
+        assert!(mass_q < mass);
+
         // H2 mobility storage fueling conditions (87.5 MPa, 80°C)
         let p = 87.5 * 1e6 + 101325.0;
         let t = 85.0 + 273.15;
         let mass = h2.specific_mass::<E>(p, t);
-        assert_float_eq!(mass, h2_storage_mass, r2nd <= 0.07);
+        assert_float_eq!(mass, h2_storage_mass, r2nd <= 0.055);
+    }
+
+    #[test]
+    fn supercritical() {
+        let co2 = compounds::CO2;
+        assert!(co2.is_supercritical(350.0));
+        assert!(!co2.is_supercritical(250.0));
+        assert_eq!(co2.saturation_pressure::<eos::PengRobinson>(350.0), None);
+        assert!(co2.saturation_pressure::<eos::PengRobinson>(250.0).is_some());
+
+        // the pseudo-critical temperature of air is about 132 K
+        let air = compounds::dry_air();
+        assert!(air.is_supercritical(150.0));
+        assert!(!air.is_supercritical(120.0));
+
+        // the critical temperature of H2 depends on the temperature
+        let h2 = compounds::H2;
+        assert!(h2.is_supercritical(h2.critical_point(30.0).t));
+    }
+
+    #[test]
+    fn critical_volume_estimate() {
+        let n2 = compounds::N2;
+        let cs = n2.critical_state;
+        let est = Pvt::critical_from_tp(cs.t, cs.p, n2.acentric_factor());
+        assert_eq!((est.p, est.t), (cs.p, cs.t));
+        assert_float_eq!(est.v, cs.v, r2nd <= 0.1);
+
+        // the estimate is consistent with the acentric factor estimated from Zc
+        let m = Molecule::from_critical(cs.t, cs.p, est.v, n2.acentric_factor(), n2.m);
+        assert_float_eq!(m.estimated_acentric_factor(), n2.acentric_factor(), abs <= 1e-12);
+    }
+
+    #[test]
+    fn density_and_pressure_invert_each_other() {
+        type E = eos::PengRobinson;
+        let ch4 = compounds::CH4;
+        for (p, t) in [(1e5, 288.15), (50e5, 250.0), (200e5, 350.0)] {
+            let rho = ch4.density::<E>(p, t);
+            assert_eq!(rho, ch4.specific_mass::<E>(p, t));
+            assert_float_eq!(ch4.pressure_from_density::<E>(rho, t), p, r2nd <= 1e-9);
+        }
+        let rho = 100.0;
+        let p = ch4.pressure_from_density::<E>(rho, 300.0);
+        assert_float_eq!(ch4.density::<E>(p, 300.0), rho, r2nd <= 1e-9);
+    }
+
+    #[test]
+    fn specific_mass_uncertainty() {
+        type E = eos::PengRobinson;
+        let ch4 = compounds::CH4;
+        let (p, t) = (50e5, 300.0);
+
+        let (rho, sigma) = ch4.specific_mass_with_uncertainty::<E>(p, 0.0, t, 0.0);
+        assert_eq!(rho, ch4.specific_mass::<E>(p, t));
+        assert_eq!(sigma, 0.0);
+
+        // each input alone matches a finite difference, and the uncertainty is linear in it
+        let (dp, dt) = (0.01e5, 0.1);
+        let (_, sigma_p) = ch4.specific_mass_with_uncertainty::<E>(p, dp, t, 0.0);
+        let fd = (ch4.specific_mass::<E>(p + dp, t) - ch4.specific_mass::<E>(p - dp, t)) / 2.0;
+        assert_float_eq!(sigma_p, fd, r2nd <= 1e-4);
+        let (_, sigma_2p) = ch4.specific_mass_with_uncertainty::<E>(p, 2.0 * dp, t, 0.0);
+        assert_float_eq!(sigma_2p, 2.0 * sigma_p, r2nd <= 1e-12);
+
+        let (_, sigma_t) = ch4.specific_mass_with_uncertainty::<E>(p, 0.0, t, dt);
+        let fd = (ch4.specific_mass::<E>(p, t - dt) - ch4.specific_mass::<E>(p, t + dt)) / 2.0;
+        assert_float_eq!(sigma_t, fd, r2nd <= 1e-4);
+
+        // independent contributions add in quadrature
+        let (_, sigma) = ch4.specific_mass_with_uncertainty::<E>(p, dp, t, dt);
+        assert_float_eq!(sigma, sigma_p.hypot(sigma_t), r2nd <= 1e-12);
+    }
+
+    #[test]
+    fn compressibility_and_expansion_match_finite_differences() {
+        type E = eos::PengRobinson;
+        let co2 = compounds::CO2;
+        let p = 50e5;
+        let t = 320.0;
+        let vm = co2.molar_volume::<E>(p, t);
+
+        let dp = p * 1e-5;
+        let dvdp = (co2.molar_volume::<E>(p + dp, t) - co2.molar_volume::<E>(p - dp, t)) / (2.0 * dp);
+        assert_float_eq!(co2.isothermal_compressibility::<E>(p, t), -dvdp / vm, r2nd <= 1e-5);
+
+        let dt = t * 1e-5;
+        let dvdt = (co2.molar_volume::<E>(p, t + dt) - co2.molar_volume::<E>(p, t - dt)) / (2.0 * dt);
+        assert_float_eq!(co2.thermal_expansion::<E>(p, t), dvdt / vm, r2nd <= 1e-5);
+
+        // ideal gas: κT = 1/P and β = 1/T
+        let ig = co2.isothermal_compressibility::<eos::IdealGas>(p, t);
+        assert_float_eq!(ig, 1.0 / p, r2nd <= 1e-9);
+        let ig = co2.thermal_expansion::<eos::IdealGas>(p, t);
+        assert_float_eq!(ig, 1.0 / t, r2nd <= 1e-6);
+    }
+
+    #[test]
+    fn nitrogen_joule_thomson() {
+        use eos::PengRobinson;
+
+        let n2 = compounds::N2;
+        // Cp of 29.1 J/mol.K and μJT of 0.22 K/bar at 300 K and 1 bar,
+        // which Peng-Robinson overestimates by less than 20%
+        assert_float_eq!(n2.ideal_cp(300.0).unwrap(), 29.1, r2nd <= 0.01);
+        let mu = n2.joule_thomson::<PengRobinson>(1e5, 300.0).unwrap();
+        assert_float_eq!(mu, 0.22e-5, r2nd <= 0.2);
+
+        // in the low pressure limit, μJT·Cp = T·dB/dT - B
+        let b = |t| n2.second_virial::<PengRobinson>(t);
+        let tdbdt = 300.0 * (b(300.01) - b(299.99)) / 0.02;
+        let cp = n2.isobaric_heat_capacity::<PengRobinson>(1e5, 300.0).unwrap();
+        assert_float_eq!(mu * cp, tdbdt - b(300.0), r2nd <= 0.01);
+
+        assert_eq!(compounds::BR2.joule_thomson::<PengRobinson>(1e5, 300.0), None);
+        assert!(compounds::dry_air().ideal_cp(300.0).is_some());
+
+        // the ideal Cp of the noble gases is 5/2·R
+        for m in [compounds::HE, compounds::NE, compounds::AR, compounds::KR, compounds::XE, compounds::RN] {
+            assert_float_eq!(m.ideal_cp(300.0).unwrap(), 2.5 * R, r2nd <= 1e-12);
+            assert!(m.joule_thomson::<PengRobinson>(1e5, 400.0).is_some());
+        }
+    }
+
+    #[test]
+    fn heat_capacity_ratio_of_air() {
+        use eos::{IdealGas, PengRobinson};
+
+        let air = compounds::dry_air();
+        let gamma = air.heat_capacity_ratio::<PengRobinson>(101325.0, 300.0).unwrap();
+        assert_float_eq!(gamma, 1.4, abs <= 0.005);
+
+        // the ideal gas gives Cp/(Cp - R)
+        let cp = air.ideal_cp(300.0).unwrap();
+        let gamma_ig = air.heat_capacity_ratio::<IdealGas>(101325.0, 300.0).unwrap();
+        assert_float_eq!(gamma_ig, cp / (cp - R), r2nd <= 1e-9);
+
+        // γ rises with the pressure for air at ambient temperature
+        assert!(air.heat_capacity_ratio::<PengRobinson>(200e5, 300.0).unwrap() > gamma);
+
+        assert_eq!(compounds::BR2.heat_capacity_ratio::<PengRobinson>(1e5, 300.0), None);
+    }
+
+    #[test]
+    fn isentropic_compression() {
+        use eos::{IdealGas, PengRobinson};
+
+        // constant Cp of monatomic gases, with γ = 5/3
+        let ar = compounds::AR;
+        let t2 = ar.isentropic_temperature::<IdealGas>(1e5, 300.0, 10e5).unwrap();
+        assert_float_eq!(t2, 300.0 * 10f64.powf(0.4), r2nd <= 1e-8);
+        let t2 = ar.isentropic_temperature::<IdealGas>(10e5, 300.0, 1e5).unwrap();
+        assert_float_eq!(t2, 300.0 * 0.1f64.powf(0.4), r2nd <= 1e-8);
+
+        // close to the ideal gas at low pressure
+        let t2 = ar.isentropic_temperature::<PengRobinson>(1e3, 300.0, 1e4).unwrap();
+        assert_float_eq!(t2, 300.0 * 10f64.powf(0.4), r2nd <= 1e-4);
+
+        // air with γ close to 1.4
+        let air = compounds::dry_air();
+        let t2 = air.isentropic_temperature::<PengRobinson>(1e5, 300.0, 10e5).unwrap();
+        assert_float_eq!(t2, 300.0 * 10f64.powf(0.4 / 1.4), r2nd <= 0.01);
+
+        assert_eq!(compounds::BR2.isentropic_temperature::<PengRobinson>(1e5, 400.0, 2e5), None);
+
+        let options = crate::SolverOptions {
+            max_iter: 2,
+            ..Default::default()
+        };
+        let res = air.isentropic_temperature_with_options::<PengRobinson>(1e5, 300.0, 10e5, &options);
+        assert_eq!(res, Err(crate::SolverError::DidNotConverge { iterations: 2 }));
+        let res = compounds::BR2.isentropic_temperature_with_options::<PengRobinson>(1e5, 400.0, 2e5, &options);
+        assert_eq!(res, Err(crate::SolverError::NoSolution));
+    }
+
+    #[test]
+    fn hydrogen_inversion_curve() {
+        use eos::RedlichKwong;
+
+        // Peng-Robinson overestimates it (above 350 K) because of the negative acentric factor
+        let h2 = compounds::H2;
+        let curve = h2.inversion_curve::<RedlichKwong>(&[1e5, 1e7, 1e9]);
+        // no inversion at 10 kbar
+        assert_eq!(curve.len(), 2);
+        // the low pressure inversion temperature of hydrogen is about 200 K
+        let (p, t) = curve[0];
+        assert_eq!(p, 1e5);
+        assert_float_eq!(t, 200.0, r2nd <= 0.15);
+
+        // the gas cools down on expansion below the inversion temperature
+        let mu = |t| h2.joule_thomson::<RedlichKwong>(1e5, t).unwrap();
+        assert!(mu(t - 10.0) > 0.0);
+        assert!(mu(t + 10.0) < 0.0);
+
+        // the unconverged points are omitted
+        let options = crate::SolverOptions {
+            max_iter: 1,
+            ..Default::default()
+        };
+        assert!(h2.inversion_curve_with_options::<RedlichKwong>(&[1e5, 1e7], &options).is_empty());
+    }
+
+    #[test]
+    fn isothermal_compression_work() {
+        use eos::{IdealGas, PengRobinson};
+
+        let ch4 = compounds::CH4;
+        let t = 300.0;
+        let ideal = R * t * (10.0f64).ln();
+        assert_float_eq!(ch4.isothermal_work::<IdealGas>(1e5, 10e5, t), ideal, r2nd <= 1e-12);
+        assert_float_eq!(ch4.isothermal_work::<PengRobinson>(1e2, 1e3, t), ideal, r2nd <= 1e-4);
+
+        // at constant temperature, Vm·dP = dG = RT·d(ln f)
+        let (p1, p2) = (1e5, 200e5);
+        let ln_f = |p: f64| (p * ch4.fugacity_coefficient::<PengRobinson>(p, t)).ln();
+        let work = ch4.isothermal_work::<PengRobinson>(p1, p2, t);
+        assert_float_eq!(work, R * t * (ln_f(p2) - ln_f(p1)), r2nd <= 1e-8);
+        assert!(work < R * t * (p2 / p1).ln());
+    }
+
+    #[test]
+    fn z_polynomial_solves_to_z() {
+        use eos::PengRobinson;
+
+        let co2 = compounds::CO2;
+        let (p, t) = (50e5, 320.0);
+        let [a3, a2, a1, a0] = co2.z_polynomial::<PengRobinson>(p, t);
+        let roots = roots::find_roots_cubic(a3, a2, a1, a0);
+        let z = roots.as_ref().iter().copied().fold(f64::NAN, f64::max);
+        assert_float_eq!(z, co2.z::<PengRobinson>(p, t), r2nd <= 1e-9);
+    }
+
+    #[test]
+    fn bracketed_molar_volume() {
+        use super::Phase;
+        use eos::{IdealGas, PengRobinson, SoaveRedlichKwong};
+
+        fn check<E: eos::EquationOfState>(gas: &impl State, p: f64, t: f64) {
+            let vm = gas.molar_volume::<E>(p, t);
+            for phase in [Phase::Vapor, Phase::Liquid] {
+                let bracketed = gas.molar_volume_bracketed::<E>(p, t, phase).unwrap();
+                assert_float_eq!(bracketed, vm, r2nd <= 1e-8);
+            }
+        }
+
+        let air = compounds::dry_air();
+        check::<PengRobinson>(&air, 200e5, 288.15);
+        check::<SoaveRedlichKwong>(&air, 1e5, 288.15);
+        check::<IdealGas>(&air, 10e5, 300.0);
+        check::<PengRobinson>(&compounds::CO2, 100e5, 350.0);
+
+        // in the two-phase region, the liquid and vapor volumes are the extreme roots
+        let co2 = compounds::CO2;
+        let (p, t) = (40e5, 280.0);
+        let liquid = co2.molar_volume_bracketed::<PengRobinson>(p, t, Phase::Liquid).unwrap();
+        let vapor = co2.molar_volume_bracketed::<PengRobinson>(p, t, Phase::Vapor).unwrap();
+        let PhaseRoots::Triple { liquid: zl, vapor: zv, .. } = co2.phase_roots::<PengRobinson>(p, t) else {
+            panic!("Should be in the two-phase region");
+        };
+        assert_float_eq!(liquid * p / (R * t), zl, r2nd <= 1e-8);
+        assert_float_eq!(vapor * p / (R * t), zv, r2nd <= 1e-8);
+    }
+
+    #[test]
+    fn co2_phase_region() {
+        use super::PhaseRegion;
+        use eos::{IdealGas, PengRobinson};
+
+        let co2 = compounds::CO2;
+        assert_eq!(co2.phase_region::<PengRobinson>(100e5, 350.0), PhaseRegion::SinglePhase);
+        assert_eq!(co2.phase_region::<PengRobinson>(40e5, 280.0), PhaseRegion::TwoPhaseRegion);
+        assert_eq!(co2.phase_region::<PengRobinson>(1e5, 280.0), PhaseRegion::SinglePhase);
+        let cs = co2.critical_state;
+        assert_eq!(co2.phase_region::<PengRobinson>(cs.p, cs.t), PhaseRegion::Critical);
+        assert_eq!(co2.phase_region::<IdealGas>(40e5, 280.0), PhaseRegion::SinglePhase);
+    }
+
+    #[test]
+    fn near_critical_warning() {
+        use crate::StateWarning;
+        type E = eos::PengRobinson;
+
+        let co2 = compounds::CO2;
+        let cs = co2.critical_state;
+        assert!(co2.near_critical(cs.p, cs.t));
+        assert!(!co2.near_critical(1e5, 300.0));
+        assert!(!co2.near_critical(cs.p, 400.0));
+
+        let (z, warning) = co2.try_z::<E>(cs.p, cs.t).unwrap();
+        assert_eq!(z, co2.z::<E>(cs.p, cs.t));
+        assert_eq!(warning, Some(StateWarning::NearCritical));
+        assert_eq!(co2.try_z::<E>(1e5, 300.0).unwrap().1, None);
+
+        // mixtures use the pseudo-critical point
+        let air = compounds::dry_air();
+        let cs = air.pseudo_critical();
+        assert!(air.near_critical(cs.p, cs.t));
+        assert!(!air.near_critical(200e5, 288.15));
+
+        // a state that doesn't provide its critical point
+        struct Wrapped(Molecule);
+        impl State for Wrapped {
+            fn molar_mass(&self) -> f64 {
+                self.0.molar_mass()
+            }
+            fn eos_params<E: eos::EquationOfState>(&self, t: f64) -> E::Params {
+                self.0.eos_params::<E>(t)
+            }
+        }
+        let wrapped = Wrapped(co2);
+        let cs = wrapped.critical_point(300.0);
+        assert_float_eq!(cs.t, co2.critical_state.t, rmax <= 1e-12);
+        assert_float_eq!(cs.p, co2.critical_state.p, rmax <= 1e-12);
+        assert!(wrapped.near_critical(cs.p, cs.t));
+        assert!(!wrapped.near_critical(1e5, 300.0));
+    }
+
+    #[test]
+    fn z_from_vm_round_trips() {
+        fn check<E: eos::EquationOfState>() {
+            let co2 = compounds::CO2;
+            for (p, t) in [(1e5, 300.0), (50e5, 320.0), (200e5, 400.0)] {
+                let vm = co2.molar_volume::<E>(p, t);
+                assert_float_eq!(co2.z_from_vm::<E>(vm, t), co2.z::<E>(p, t), r2nd <= 1e-9);
+            }
+        }
+        check::<eos::IdealGas>();
+        check::<eos::VanDerWaals>();
+        check::<eos::SoaveRedlichKwong>();
+        check::<eos::PengRobinson>();
+        check::<eos::LeeKesler>();
+    }
+
+    #[test]
+    fn argon_second_virial_sign() {
+        fn check<E: eos::EquationOfState>() {
+            let ar = compounds::AR;
+            let tb = ar.boyle_temperature::<E>().unwrap();
+            assert!(ar.second_virial::<E>(0.8 * tb) < 0.0);
+            assert!(ar.second_virial::<E>(1.2 * tb) > 0.0);
+        }
+        check::<eos::VanDerWaals>();
+        check::<eos::RedlichKwong>();
+        check::<eos::SoaveRedlichKwong>();
+        check::<eos::PengRobinson>();
+
+        // experimental B of argon at 273.15 K is about -21.5 cm3/mol,
+        // the cubic equations only give its order of magnitude (PR: -29 cm3/mol)
+        let b = compounds::AR.second_virial::<eos::PengRobinson>(273.15);
+        assert!(b > -40e-6 && b < -10e-6);
+    }
+
+    #[test]
+    fn isochoric_heating() {
+        type E = eos::PengRobinson;
+        let n2 = compounds::N2;
+
+        // a vessel filled at 200 bar and 15°C, then heated
+        let vm = n2.molar_volume::<E>(200e5, 288.15);
+        let temperatures: Vec<f64> = (0..=20).map(|i| 288.15 + i as f64 * 10.0).collect();
+        let pressures = n2.pressure_isochoric::<E>(vm, &temperatures);
+
+        assert_eq!(pressures.len(), temperatures.len());
+        assert_float_eq!(pressures[0], 200e5, r2nd <= 1e-9);
+        assert!(pressures.windows(2).all(|w| w[1] > w[0]));
+    }
+
+    #[test]
+    fn air_reference_densities() {
+        type E = eos::PengRobinson;
+        let air = crate::Gas::from(compounds::dry_air());
+
+        // ISA sea level (15 °C, 1 atm)
+        assert_float_eq!(air.specific_mass::<E>(101325.0, 288.15), 1.225, abs <= 0.002);
+        assert_float_eq!(air.standard_density::<E>(), 1.2922, abs <= 0.002);
+        assert_float_eq!(air.normal_density::<E>(), 1.2041, abs <= 0.002);
+    }
+
+    #[test]
+    fn z_grid_matches_z() {
+        type E = eos::PengRobinson;
+        let air = compounds::dry_air();
+        let pressures = [1e5, 10e5, 100e5];
+        let temperatures = [200.0, 300.0];
+        let grid = air.z_grid::<E>(&pressures, &temperatures);
+        assert_eq!(grid.len(), 2);
+        for (row, t) in grid.iter().zip(temperatures) {
+            for (z, p) in row.iter().zip(pressures) {
+                assert_eq!(*z, air.z::<E>(p, t));
+            }
+        }
+    }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn z_grid_par_matches_sequential() {
+        type E = eos::PengRobinson;
+        let gas = compounds::natural_gas();
+        let pressures: Vec<f64> = (1..=100).map(|i| i as f64 * 2e5).collect();
+        let temperatures: Vec<f64> = (0..50).map(|i| 200.0 + i as f64 * 5.0).collect();
+        assert_eq!(
+            gas.z_grid_par::<E>(&pressures, &temperatures),
+            gas.z_grid::<E>(&pressures, &temperatures)
+        );
+    }
+
+    #[test]
+    fn mass_basis() {
+        type E = eos::PengRobinson;
+        let air = compounds::dry_air();
+        // about 287 J/kg.K
+        assert_float_eq!(air.specific_gas_constant(), 287.0, abs <= 0.1);
+        assert_float_eq!(
+            air.specific_gas_constant(),
+            super::R / air.molar_mass(),
+            ulps <= 1
+        );
+
+        for (p, t) in [(1e5, 288.15), (200e5, 250.0), (700e5, 400.0)] {
+            let sv = air.specific_volume::<E>(p, t);
+            assert_float_eq!(sv, 1.0 / air.specific_mass::<E>(p, t), r2nd <= 1e-12);
+        }
+    }
+
+    #[test]
+    fn pvt_and_ptz_from_pt() {
+        type E = eos::PengRobinson;
+        let air = compounds::dry_air();
+        let (p, t) = (200e5, 288.15);
+        let z = air.z::<E>(p, t);
+
+        let ptz = air.ptz_from_pt::<E>(p, t);
+        assert_eq!(ptz, crate::Ptz { p, t, z });
+
+        let pvt = air.pvt_from_pt::<E>(p, t);
+        assert_eq!(pvt.v, air.molar_volume::<E>(p, t));
+        assert_float_eq!(pvt.z(), z, r2nd <= 1e-12);
+    }
+
+    #[test]
+    fn phase_roots_in_two_phase_region() {
+        use crate::PhaseRoots;
+        type E = eos::PengRobinson;
+
+        let co2 = compounds::CO2;
+        let t = 280.0;
+        let psat = co2.saturation_pressure::<E>(t).unwrap();
+        match co2.phase_roots::<E>(psat, t) {
+            PhaseRoots::Triple {
+                liquid,
+                unstable,
+                vapor,
+            } => {
+                assert!(liquid < unstable && unstable < vapor);
+                assert_eq!(vapor, co2.z::<E>(psat, t));
+            }
+            roots => panic!("expected three roots, got {roots:?}"),
+        }
+
+        // supercritical
+        let z = co2.z::<E>(100e5, 350.0);
+        assert_eq!(co2.phase_roots::<E>(100e5, 350.0), PhaseRoots::Single(z));
+    }
+
+    #[test]
+    fn enthalpy_and_entropy_from_reference() {
+        use crate::ReferenceState;
+        type E = eos::PengRobinson;
+
+        // only the departure remains at the reference state
+        let n2 = compounds::N2;
+        let reference = ReferenceState::STANDARD;
+        let (p, t) = (reference.p, reference.t);
+        let h = n2.enthalpy::<E>(p, t, &reference).unwrap();
+        assert_float_eq!(h, n2.enthalpy_departure::<E>(p, t), abs <= 1e-9);
+        assert!(h.abs() < 10.0);
+        let s = n2.entropy::<E>(p, t, &reference).unwrap();
+        assert_float_eq!(s, n2.entropy_departure::<E>(p, t), abs <= 1e-12);
+        assert!(s.abs() < 0.1);
+
+        // heating at low pressure takes about Cp·ΔT, and shifts with the reference
+        let shifted = ReferenceState { h: 1000.0, ..reference };
+        let h400 = n2.enthalpy::<E>(p, 400.0, &shifted).unwrap();
+        assert_float_eq!(h400 - 1000.0, 29.2 * (400.0 - t), rmax <= 0.01);
+
+        // isentropic compression conserves the entropy
+        let t2 = n2.isentropic_temperature::<E>(1e5, 300.0, 10e5).unwrap();
+        let s1 = n2.entropy::<E>(1e5, 300.0, &reference).unwrap();
+        assert_float_eq!(n2.entropy::<E>(10e5, t2, &reference).unwrap(), s1, abs <= 1e-6);
+
+        assert_eq!(compounds::BR2.enthalpy::<E>(p, t, &reference), None);
+    }
+
+    #[test]
+    fn z_f32_matches_f64() {
+        type E = eos::PengRobinson;
+        let n2 = compounds::N2;
+        for (p, t) in [(1e5f32, 300f32), (200e5, 150.0), (700e5, 400.0)] {
+            let z = n2.z::<E>(f64::from(p), f64::from(t));
+            assert_float_eq!(f64::from(n2.z_f32::<E>(p, t)), z, rmax <= f64::from(f32::EPSILON));
+        }
+    }
+
+    #[test]
+    fn roots_below_covolume_are_rejected() {
+        use crate::{StateError, eos::{AbParams, EquationOfState}};
+
+        // at very high pressure, the volume stays above the covolume
+        fn verify<E: EquationOfState>(m: &Molecule) {
+            let (p, t) = (1e10, 300.0);
+            let (z, _) = m.try_z::<E>(p, t).unwrap();
+            let b = m.eos_params_report::<E>(p, t).unwrap().b_dimensionless;
+            assert!(z > b, "{}: Z = {z}, B = {b}", E::NAME);
+        }
+        let n2 = compounds::N2;
+        verify::<eos::VanDerWaals>(&n2);
+        verify::<eos::PengRobinson>(&n2);
+        verify::<eos::PatelTejaValderrama>(&n2);
+
+        // Van der Waals with a root at half the covolume
+        enum Collapsed {}
+        impl EquationOfState for Collapsed {
+            const NAME: &'static str = "Collapsed";
+            type Params = AbParams;
+            fn params(cs: &Pvt, w: f64, t: f64) -> AbParams {
+                eos::VanDerWaals::params(cs, w, t)
+            }
+            fn pressure(params: &AbParams, vm: f64, t: f64) -> f64 {
+                eos::VanDerWaals::pressure(params, vm, t)
+            }
+            fn z_polyn(params: &AbParams, p: f64, t: f64) -> [f64; 4] {
+                [0.0, 0.0, 1.0, -0.5 * params.b * p / (R * t)]
+            }
+            fn params_report(params: &AbParams, p: f64, t: f64) -> Option<eos::ParamsReport> {
+                eos::VanDerWaals::params_report(params, p, t)
+            }
+        }
+        let (p, t) = (1e8, 300.0);
+        assert_eq!(n2.try_z::<Collapsed>(p, t), Err(StateError::BelowCovolume { eos: "Collapsed", p, t }));
+        let params = n2.eos_params::<Collapsed>(t);
+        assert!(eos::z_roots::<Collapsed>(&params, p, t).is_empty());
+    }
+
+    #[test]
+    fn z_iter_matches_z() {
+        type E = eos::PengRobinson;
+        let air = compounds::dry_air();
+        let pressures = (1..=100).map(|i| i as f64 * 1e6);
+        let zs: Vec<f64> = air.z_iter::<E>(pressures.clone(), 250.0).collect();
+        assert_eq!(zs.len(), 100);
+        assert!(pressures.zip(zs).all(|(p, z)| z == air.z::<E>(p, 250.0)));
+
+        // lazy: only the pressures taken are evaluated
+        let mut iter = air.z_iter::<E>((1..).map(|i| i as f64 * 1e5), 250.0);
+        assert_eq!(iter.nth(9), Some(air.z::<E>(10e5, 250.0)));
+    }
+
+    #[test]
+    fn z_all_eos_compares_equations() {
+        use crate::{StateEos, eos::Eos};
+
+        let co2 = compounds::CO2;
+        let all = co2.z_all_eos(100e5, 350.0);
+        assert_eq!(all.len(), Eos::all().len());
+        assert!(all.iter().map(|(eos, _)| eos).eq(Eos::all()));
+        assert_eq!(all[0], (Eos::IdealGas, 1.0));
+        for &(eos, z) in &all[1..] {
+            assert!((z - 1.0).abs() > 0.1, "{eos:?}: {z}");
+            assert_eq!(z, co2.z_eos(eos, 100e5, 350.0));
+        }
+    }
+
+    #[test]
+    fn zero_pressure_is_rejected() {
+        use crate::{ExtensiveState, StateError};
+
+        type E = eos::PengRobinson;
+        let n2 = compounds::N2;
+        let t = 300.0;
+
+        assert!(n2.molar_volume::<E>(0.0, t).is_infinite());
+        assert_eq!(n2.specific_mass::<E>(0.0, t), 0.0);
+
+        let err = StateError::NonPositivePressure { p: 0.0 };
+        assert_eq!(n2.try_molar_volume::<E>(0.0, t), Err(err));
+        assert_eq!(n2.try_specific_mass::<E>(0.0, t), Err(err));
+        assert_eq!(n2.try_volume::<E>(0.0, 1.0, t), Err(err));
+        assert_eq!(n2.try_molar_volume::<E>(-1e5, t), Err(StateError::NonPositivePressure { p: -1e5 }));
+        assert!(matches!(
+            n2.try_molar_volume::<E>(f64::NAN, t),
+            Err(StateError::NonFinite { p, .. }) if p.is_nan()
+        ));
+
+        let p = 1e5;
+        assert_eq!(n2.try_molar_volume::<E>(p, t), Ok(n2.molar_volume::<E>(p, t)));
+        assert_float_eq!(n2.try_specific_mass::<E>(p, t).unwrap(), n2.specific_mass::<E>(p, t), r2nd <= 1e-15);
+        assert_float_eq!(n2.try_volume::<E>(p, 2.0, t).unwrap(), n2.volume::<E>(p, 2.0, t), r2nd <= 1e-15);
+    }
+
+    #[test]
+    fn h2_quantum_correction_improves_z() {
+        type E = eos::PengRobinson;
+        let exp = include_str!("../bench/exp/z_h2.csv");
+
+        let h2 = compounds::H2;
+        let h2q = compounds::H2.with_quantum_correction(compounds::H2_QUANTUM_CORRECTION);
+
+        let mut lines = exp.lines();
+        let pressures: Vec<f64> = lines
+            .next()
+            .unwrap()
+            .split(',')
+            .skip(1)
+            .map(|p| p.parse::<f64>().unwrap() * 1e5)
+            .collect();
+
+        let mut err = 0.0;
+        let mut err_q = 0.0;
+        for line in lines {
+            let mut fields = line.split(',');
+            let t: f64 = fields.next().unwrap().parse().unwrap();
+            if !(40.0..=300.0).contains(&t) {
+                continue;
+            }
+            for (p, z) in pressures.iter().zip(fields) {
+                let z: f64 = z.parse().unwrap();
+                if z.is_nan() {
+                    continue;
+                }
+                err += (h2.z::<E>(*p, t) - z).abs() / z;
+                err_q += (h2q.z::<E>(*p, t) - z).abs() / z;
+            }
+        }
+        assert!(err_q < err, "corrected: {err_q}, uncorrected: {err}");
     }
 }
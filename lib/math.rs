@@ -0,0 +1,300 @@
+//! Numerical support for both `std` and `no_std` builds.
+//!
+//! With `std`, the root finding is provided by the `roots` crate.
+//! Without it, the float functions are provided by `libm` through the [`Float`] trait,
+//! and the root finding by the minimal implementations of this module, that mirror the `roots` API.
+//! They are needed because `roots` has no `no_std` support (it uses `std` unconditionally
+//! and has no feature to disable it). Only the quadratic and cubic solvers and the Brent method
+//! used by this crate are implemented, and they are tested against `roots` in `std` builds.
+
+#[cfg(feature = "std")]
+pub(crate) use roots::{SearchError, SimpleConvergency, find_root_brent, find_roots_cubic, find_roots_quadratic};
+
+#[cfg(not(feature = "std"))]
+pub(crate) use self::solvers::*;
+
+/// The float functions of `std`, implemented with `libm`
+#[cfg(all(not(feature = "std"), not(test)))]
+pub(crate) trait Float {
+    fn sqrt(self) -> Self;
+    fn cbrt(self) -> Self;
+    fn powf(self, n: Self) -> Self;
+    fn powi(self, n: i32) -> Self;
+    fn exp(self) -> Self;
+    fn ln(self) -> Self;
+    fn cos(self) -> Self;
+    fn acos(self) -> Self;
+    fn hypot(self, other: Self) -> Self;
+    fn log10(self) -> Self;
+    fn floor(self) -> Self;
+}
+
+#[cfg(all(not(feature = "std"), not(test)))]
+impl Float for f64 {
+    fn sqrt(self) -> f64 {
+        libm::sqrt(self)
+    }
+
+    fn cbrt(self) -> f64 {
+        libm::cbrt(self)
+    }
+
+    fn powf(self, n: f64) -> f64 {
+        libm::pow(self, n)
+    }
+
+    fn powi(self, n: i32) -> f64 {
+        libm::pow(self, n as f64)
+    }
+
+    fn exp(self) -> f64 {
+        libm::exp(self)
+    }
+
+    fn ln(self) -> f64 {
+        libm::log(self)
+    }
+
+    fn cos(self) -> f64 {
+        libm::cos(self)
+    }
+
+    fn acos(self) -> f64 {
+        libm::acos(self)
+    }
+
+    fn hypot(self, other: f64) -> f64 {
+        libm::hypot(self, other)
+    }
+
+    fn log10(self) -> f64 {
+        libm::log10(self)
+    }
+
+    fn floor(self) -> f64 {
+        libm::floor(self)
+    }
+}
+
+#[cfg(any(test, not(feature = "std")))]
+mod solvers {
+    #[cfg(all(not(feature = "std"), not(test)))]
+    use super::Float;
+
+    /// The real roots of a polynomial
+    pub(crate) struct Roots {
+        roots: [f64; 3],
+        len: usize,
+    }
+
+    impl Roots {
+        fn new(roots: &[f64]) -> Self {
+            let mut res = Roots {
+                roots: [0.0; 3],
+                len: roots.len(),
+            };
+            res.roots[..roots.len()].copy_from_slice(roots);
+            res
+        }
+    }
+
+    impl AsRef<[f64]> for Roots {
+        fn as_ref(&self) -> &[f64] {
+            &self.roots[..self.len]
+        }
+    }
+
+    /// The real roots of `a2*x^2 + a1*x + a0 = 0`
+    pub(crate) fn find_roots_quadratic(a2: f64, a1: f64, a0: f64) -> Roots {
+        if a2 == 0.0 {
+            return if a1 == 0.0 {
+                Roots::new(&[])
+            } else {
+                Roots::new(&[-a0 / a1])
+            };
+        }
+        let disc = a1 * a1 - 4.0 * a2 * a0;
+        if disc < 0.0 {
+            Roots::new(&[])
+        } else if disc == 0.0 {
+            Roots::new(&[-a1 / (2.0 * a2)])
+        } else {
+            // avoid the cancellation of the smallest root
+            let q = -0.5 * (a1 + a1.signum() * disc.sqrt());
+            let (x1, x2) = (q / a2, a0 / q);
+            if x1 < x2 {
+                Roots::new(&[x1, x2])
+            } else {
+                Roots::new(&[x2, x1])
+            }
+        }
+    }
+
+    /// The real roots of `a3*x^3 + a2*x^2 + a1*x + a0 = 0`
+    pub(crate) fn find_roots_cubic(a3: f64, a2: f64, a1: f64, a0: f64) -> Roots {
+        if a3 == 0.0 {
+            return find_roots_quadratic(a2, a1, a0);
+        }
+        // depressed cubic t^3 + p*t + q = 0, with x = t - b/3
+        let (b, c, d) = (a2 / a3, a1 / a3, a0 / a3);
+        let p = c - b * b / 3.0;
+        let q = 2.0 * b * b * b / 27.0 - b * c / 3.0 + d;
+        let shift = -b / 3.0;
+
+        let disc = q * q / 4.0 + p * p * p / 27.0;
+        if disc > 0.0 {
+            let s = disc.sqrt();
+            Roots::new(&[(-q / 2.0 + s).cbrt() + (-q / 2.0 - s).cbrt() + shift])
+        } else if p == 0.0 {
+            Roots::new(&[shift])
+        } else {
+            // three real roots, with the trigonometric solution
+            let r = 2.0 * (-p / 3.0).sqrt();
+            let phi = (3.0 * q / (p * r)).clamp(-1.0, 1.0).acos() / 3.0;
+            let third = 2.0 * core::f64::consts::PI / 3.0;
+            let mut roots = [
+                r * phi.cos() + shift,
+                r * (phi - third).cos() + shift,
+                r * (phi - 2.0 * third).cos() + shift,
+            ];
+            roots.sort_by(|a, b| a.partial_cmp(b).unwrap());
+            Roots::new(&roots)
+        }
+    }
+
+    /// The convergence criteria of [`find_root_brent`]
+    pub(crate) struct SimpleConvergency {
+        /// The tolerance on the root
+        pub eps: f64,
+        /// The maximum number of iterations
+        pub max_iter: usize,
+    }
+
+    /// The reason a root could not be found
+    #[derive(Debug)]
+    pub(crate) enum SearchError {
+        /// The maximum number of iterations was reached
+        NoConvergency,
+        /// The function has the same sign at both ends of the interval
+        NoBracketing,
+    }
+
+    /// Find a root of `f` between `a` and `b` with the Brent method
+    pub(crate) fn find_root_brent<F: Fn(f64) -> f64>(
+        a: f64,
+        b: f64,
+        f: F,
+        convergency: &mut SimpleConvergency,
+    ) -> Result<f64, SearchError> {
+        let (mut a, mut b) = (a, b);
+        let (mut fa, mut fb) = (f(a), f(b));
+        if fa == 0.0 {
+            return Ok(a);
+        }
+        if fb == 0.0 {
+            return Ok(b);
+        }
+        if fa.signum() == fb.signum() {
+            return Err(SearchError::NoBracketing);
+        }
+        let (mut c, mut fc) = (a, fa);
+        let mut d = b - a;
+        let mut e = d;
+        for _ in 0..convergency.max_iter {
+            if fb.signum() == fc.signum() {
+                (c, fc) = (a, fa);
+                d = b - a;
+                e = d;
+            }
+            if fc.abs() < fb.abs() {
+                (a, fa) = (b, fb);
+                (b, fb) = (c, fc);
+                (c, fc) = (a, fa);
+            }
+            let tol = 2.0 * f64::EPSILON * b.abs() + 0.5 * convergency.eps;
+            let m = 0.5 * (c - b);
+            if m.abs() <= tol || fb == 0.0 {
+                return Ok(b);
+            }
+            if e.abs() >= tol && fa.abs() > fb.abs() {
+                // inverse quadratic interpolation, or secant if a == c
+                let s = fb / fa;
+                let (mut p, mut q) = if a == c {
+                    (2.0 * m * s, 1.0 - s)
+                } else {
+                    let q = fa / fc;
+                    let r = fb / fc;
+                    (
+                        s * (2.0 * m * q * (q - r) - (b - a) * (r - 1.0)),
+                        (q - 1.0) * (r - 1.0) * (s - 1.0),
+                    )
+                };
+                if p > 0.0 {
+                    q = -q;
+                } else {
+                    p = -p;
+                }
+                if 2.0 * p < (3.0 * m * q - (tol * q).abs()).min((e * q).abs()) {
+                    e = d;
+                    d = p / q;
+                } else {
+                    d = m;
+                    e = m;
+                }
+            } else {
+                d = m;
+                e = m;
+            }
+            (a, fa) = (b, fb);
+            b += if d.abs() > tol { d } else { tol.copysign(m) };
+            fb = f(b);
+        }
+        Err(SearchError::NoConvergency)
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::solvers;
+    use float_eq::assert_float_eq;
+
+    fn assert_roots_eq(actual: &[f64], expected: &[f64]) {
+        assert_eq!(actual.len(), expected.len());
+        for (a, e) in actual.iter().zip(expected) {
+            assert_float_eq!(*a, *e, abs <= 1e-9);
+        }
+    }
+
+    #[test]
+    fn quadratic_matches_roots() {
+        for (a2, a1, a0) in [(1.0, -3.0, 2.0), (2.0, 0.0, 1.0), (1.0, 2.0, 1.0), (0.0, 2.0, -1.0)] {
+            let expected = roots::find_roots_quadratic(a2, a1, a0);
+            assert_roots_eq(solvers::find_roots_quadratic(a2, a1, a0).as_ref(), expected.as_ref());
+        }
+    }
+
+    #[test]
+    fn cubic_matches_roots() {
+        // Z cubics with one and three real roots
+        for (a3, a2, a1, a0) in [
+            (1.0, -1.0, 0.2, -0.01),
+            (1.0, -0.95, 0.2, -0.01),
+            (1.0, -6.0, 11.0, -6.0),
+            (1.0, 0.0, 0.0, -8.0),
+        ] {
+            let expected = roots::find_roots_cubic(a3, a2, a1, a0);
+            assert_roots_eq(solvers::find_roots_cubic(a3, a2, a1, a0).as_ref(), expected.as_ref());
+        }
+    }
+
+    #[test]
+    fn brent() {
+        let mut convergency = solvers::SimpleConvergency {
+            eps: 1e-12,
+            max_iter: 100,
+        };
+        let root = solvers::find_root_brent(0.0, 2.0, |x| x * x - 2.0, &mut convergency).unwrap();
+        assert_float_eq!(root, 2f64.sqrt(), abs <= 1e-12);
+        assert!(solvers::find_root_brent(0.0, 1.0, |x| x * x + 1.0, &mut convergency).is_err());
+    }
+}
@@ -0,0 +1,151 @@
+#[cfg(feature = "std")]
+use std::collections::HashMap;
+
+use crate::{R, State, eos::EquationOfState};
+
+/// The state of a gas prepared at a fixed temperature.
+///
+/// Holds the equation of state parameters computed for the temperature,
+/// so that evaluating many pressures does not recompute them.
+/// Created with [`State::prepare`].
+pub struct PreparedState<'a, S, E: EquationOfState> {
+    gas: &'a S,
+    params: E::Params,
+    t: f64,
+}
+
+impl<'a, S: State, E: EquationOfState> PreparedState<'a, S, E> {
+    /// Prepare the state of `gas` at the temperature `t`, in K
+    pub fn new(gas: &'a S, t: f64) -> Self {
+        let params = gas.eos_params::<E>(t);
+        PreparedState { gas, params, t }
+    }
+
+    /// The gas this state was prepared for
+    pub fn gas(&self) -> &'a S {
+        self.gas
+    }
+
+    /// The temperature of this state, in K
+    pub fn temperature(&self) -> f64 {
+        self.t
+    }
+
+    /// The equation of state parameters at the temperature of this state
+    pub fn params(&self) -> &E::Params {
+        &self.params
+    }
+
+    /// Compute the pressure of the gas for the molar volume
+    pub fn pressure(&self, vm: f64) -> f64 {
+        E::pressure(&self.params, vm, self.t)
+    }
+
+    /// Compute the compression factor Z such as Z = PV/RT
+    ///
+    /// # Panics
+    /// This function will panic of no positive real root can be found, which is generally
+    /// an indication that the parameters have physical non-sense.
+    pub fn z(&self, p: f64) -> f64 {
+        E::z(&self.params, p, self.t).expect("Should have a found a positive real root")
+    }
+
+    /// Compute the molar volume the gas in m^3/mol
+    pub fn molar_volume(&self, p: f64) -> f64 {
+        let z = self.z(p);
+        z * R * self.t / p
+    }
+
+    /// Compute the specific mass of the gas in kg/m^3
+    pub fn specific_mass(&self, p: f64) -> f64 {
+        let z = self.z(p);
+        self.gas.molar_mass() * p / (z * R * self.t)
+    }
+}
+
+/// A cache of the equation of state parameters of a gas, keyed by temperature.
+///
+/// The parameters of the components and their mixing are computed once per temperature,
+/// which is useful when a computation visits the same temperatures repeatedly.
+#[cfg(feature = "std")]
+pub struct ParamsCache<'a, S, E: EquationOfState> {
+    gas: &'a S,
+    params: HashMap<u64, E::Params>,
+}
+
+#[cfg(feature = "std")]
+impl<'a, S: State, E: EquationOfState> ParamsCache<'a, S, E>
+where
+    E::Params: Clone,
+{
+    /// An empty cache for `gas`
+    pub fn new(gas: &'a S) -> Self {
+        ParamsCache {
+            gas,
+            params: HashMap::new(),
+        }
+    }
+
+    /// The state of the gas prepared at the temperature `t`, in K.
+    /// The parameters are computed only if this temperature was not visited before.
+    pub fn prepare(&mut self, t: f64) -> PreparedState<'a, S, E> {
+        let gas = self.gas;
+        let params = self
+            .params
+            .entry(t.to_bits())
+            .or_insert_with(|| gas.eos_params::<E>(t))
+            .clone();
+        PreparedState { gas, params, t }
+    }
+
+    /// The number of temperatures in the cache
+    pub fn len(&self) -> usize {
+        self.params.len()
+    }
+
+    /// Whether the cache is empty
+    pub fn is_empty(&self) -> bool {
+        self.params.is_empty()
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::ParamsCache;
+    use crate::{State, compounds, eos};
+
+    #[test]
+    fn prepared_state_matches_state() {
+        type E = eos::PengRobinson;
+
+        let air = compounds::dry_air();
+        for t in [200.0, 273.15, 400.0] {
+            let prepared = air.prepare::<E>(t);
+            for i in 1..=200 {
+                let p = i as f64 * 1e5;
+                assert_eq!(prepared.z(p), air.z::<E>(p, t));
+                assert_eq!(prepared.molar_volume(p), air.molar_volume::<E>(p, t));
+                assert_eq!(prepared.specific_mass(p), air.specific_mass::<E>(p, t));
+            }
+        }
+    }
+
+    #[test]
+    fn params_cache_matches_state() {
+        type E = eos::PengRobinson;
+
+        let gas = compounds::natural_gas();
+        let mut cache = ParamsCache::<_, E>::new(&gas);
+        assert!(cache.is_empty());
+        for _ in 0..3 {
+            for t in [250.0, 288.15, 320.0] {
+                let prepared = cache.prepare(t);
+                for i in 1..=50 {
+                    let p = i as f64 * 2e5;
+                    assert_eq!(prepared.z(p), gas.z::<E>(p, t));
+                }
+            }
+        }
+        assert_eq!(cache.len(), 3);
+    }
+}
@@ -0,0 +1,302 @@
+use crate::{
+    Molecule, R, SolverError, SolverOptions, State, eos, eos::EquationOfState, ln_fugacity_coefficient, solver,
+};
+#[cfg(all(not(feature = "std"), not(test)))]
+use crate::math::Float;
+
+impl Molecule {
+    /// Compute the saturation (vapor) pressure at the temperature `t`, in Pa.
+    ///
+    /// The saturation pressure is where liquid and vapor roots of the equation
+    /// of state have the same fugacity.
+    /// Returns `None` at or above the critical temperature, or if the equation of state
+    /// does not predict a liquid phase (such as the ideal gas or Lee-Kesler).
+    pub fn saturation_pressure<E: EquationOfState>(&self, t: f64) -> Option<f64> {
+        self.saturation_pressure_with_options::<E>(t, &SolverOptions::default())
+            .ok()
+    }
+
+    /// Compute the saturation pressure at the temperature `t` like [`Molecule::saturation_pressure`],
+    /// with the convergence control of `options`.
+    ///
+    /// Fails with [`SolverError::NoSolution`] where [`Molecule::saturation_pressure`] returns `None`.
+    pub fn saturation_pressure_with_options<E: EquationOfState>(
+        &self,
+        t: f64,
+        options: &SolverOptions,
+    ) -> Result<f64, SolverError> {
+        if self.is_supercritical(t) {
+            return Err(SolverError::NoSolution);
+        }
+        let (cs, _) = self.effective_critical_state(t);
+        let params = self.eos_params::<E>(t);
+
+        // difference of ln φ between liquid and vapor, if both exist at ln(p)
+        let delta = |ln_p: f64| {
+            let p = ln_p.exp();
+            let roots = eos::z_roots::<E>(&params, p, t);
+            if roots.len() < 3 || roots[2] - roots[0] < 1e-9 {
+                return None;
+            }
+            let ln_phi_l = ln_fugacity_coefficient::<E>(&params, roots[0], p, t);
+            let ln_phi_v = ln_fugacity_coefficient::<E>(&params, roots[2], p, t);
+            Some(ln_phi_l - ln_phi_v)
+        };
+
+        find_saturation(cs.p, delta, options)
+    }
+
+    /// Compute the saturation pressure at the temperature `t` with the equal-area construction of Maxwell, in Pa.
+    ///
+    /// The saturation pressure is where the isotherm P(Vm) of the equation of state,
+    /// integrated between the liquid and vapor volumes, gives the same area as the horizontal
+    /// line at the saturation pressure. The integral is computed by quadrature, independently
+    /// of the fugacity coefficients, so this validates [`Molecule::saturation_pressure`].
+    /// Returns `None` where [`Molecule::saturation_pressure`] does.
+    pub fn saturation_pressure_maxwell<E: EquationOfState>(&self, t: f64) -> Option<f64> {
+        self.saturation_pressure_maxwell_with_options::<E>(t, &SolverOptions::default())
+            .ok()
+    }
+
+    /// Compute the saturation pressure at the temperature `t` like [`Molecule::saturation_pressure_maxwell`],
+    /// with the convergence control of `options`.
+    ///
+    /// Fails with [`SolverError::NoSolution`] where [`Molecule::saturation_pressure_maxwell`] returns `None`.
+    pub fn saturation_pressure_maxwell_with_options<E: EquationOfState>(
+        &self,
+        t: f64,
+        options: &SolverOptions,
+    ) -> Result<f64, SolverError> {
+        if self.is_supercritical(t) {
+            return Err(SolverError::NoSolution);
+        }
+        let (cs, _) = self.effective_critical_state(t);
+        let params = self.eos_params::<E>(t);
+        let b = E::params_report(&params, cs.p, t).map_or(0.0, |r| r.b);
+
+        // difference of the areas below the isotherm and below the line at p, over RT
+        let delta = |ln_p: f64| {
+            let p = ln_p.exp();
+            let roots = eos::z_roots::<E>(&params, p, t);
+            if roots.len() < 3 || roots[2] - roots[0] < 1e-9 {
+                return None;
+            }
+            let (vl, vv) = (roots[0] * R * t / p, roots[2] * R * t / p);
+            // ∫P.dV = ∫P.(V - b).d(ln(V - b)), where the repulsive term RT/(V - b) is constant
+            let pv = |ln_v: f64| {
+                let v = b + ln_v.exp();
+                E::pressure(&params, v, t) * (v - b)
+            };
+            let area = eos::integrate(pv, (vl - b).ln(), (vv - b).ln());
+            Some((area - p * (vv - vl)) / (R * t))
+        };
+
+        find_saturation(cs.p, delta, options)
+    }
+
+    /// Compute the enthalpy of vaporization at the temperature `t`, in J/mol.
+    ///
+    /// It is given by the Clapeyron equation ΔH = T·ΔV·dPsat/dT, where ΔV is the difference
+    /// between the vapor and liquid molar volumes at the saturation pressure.
+    /// Returns `None` where [`Molecule::saturation_pressure`] does.
+    pub fn enthalpy_of_vaporization<E: EquationOfState>(&self, t: f64) -> Option<f64> {
+        if self.is_supercritical(t) {
+            return None;
+        }
+        let h = t * 1e-4;
+        let p = self.saturation_pressure::<E>(t)?;
+        let dpdt = (self.saturation_pressure::<E>(t + h)? - self.saturation_pressure::<E>(t - h)?)
+            / (2.0 * h);
+
+        let params = self.eos_params::<E>(t);
+        let roots = eos::z_roots::<E>(&params, p, t);
+        let (zl, zv) = (*roots.first()?, *roots.last()?);
+        let dv = (zv - zl) * R * t / p;
+        Some(t * dv * dpdt)
+    }
+
+    /// Compute the saturated liquid molar volume at the temperature `t`, in m^3/mol,
+    /// with the COSTALD correlation (Hankinson and Thomson, 1979).
+    ///
+    /// The critical volume is used as characteristic volume V*.
+    /// Returns `None` at or above the critical temperature.
+    pub fn saturated_liquid_volume(&self, t: f64) -> Option<f64> {
+        let (cs, _) = self.effective_critical_state(t);
+        self.saturated_liquid_volume_with(t, cs.v)
+    }
+
+    /// Compute the saturated liquid molar volume at the temperature `t`, in m^3/mol,
+    /// with the COSTALD correlation and the characteristic volume `v_star`, in m^3/mol.
+    ///
+    /// Returns `None` at or above the critical temperature.
+    pub fn saturated_liquid_volume_with(&self, t: f64, v_star: f64) -> Option<f64> {
+        if self.is_supercritical(t) {
+            return None;
+        }
+        let (cs, w) = self.effective_critical_state(t);
+        let tr = t / cs.t;
+        let tau = 1.0 - tr;
+        let v0 = 1.0 - 1.52816 * tau.powf(1.0 / 3.0) + 1.43907 * tau.powf(2.0 / 3.0) - 0.81446 * tau
+            + 0.190454 * tau.powf(4.0 / 3.0);
+        let vd = (-0.296123 + 0.386914 * tr - 0.0427258 * tr * tr - 0.0480645 * tr * tr * tr)
+            / (tr - 1.00001);
+        Some(v_star * v0 * (1.0 - w * vd))
+    }
+}
+
+/// Find the saturation pressure below `pc`, where `delta(ln p)` changes sign.
+///
+/// `delta` is the difference between the liquid and vapor phases,
+/// or `None` if the equation of state doesn't have both roots at `ln p`.
+fn find_saturation<F>(pc: f64, delta: F, options: &SolverOptions) -> Result<f64, SolverError>
+where
+    F: Fn(f64) -> Option<f64>,
+{
+    // scan the pressures downward from Pc to find a sign change within the two phases region
+    let ln_pc = pc.ln();
+    let mut hi: Option<f64> = None;
+    for i in 1..=1000 {
+        let ln_p = ln_pc - i as f64 * 0.03;
+        match (delta(ln_p), hi) {
+            (Some(d), _) if d < 0.0 => hi = Some(ln_p),
+            (Some(_), Some(ln_hi)) => {
+                // the tolerance on ln(p) is relative to p
+                let f = |ln_p: f64| delta(ln_p).unwrap_or(f64::NAN);
+                return solver::find_root_brent(ln_p, ln_hi, f, 1.0, options).map(f64::exp);
+            }
+            _ => {}
+        }
+    }
+    Err(SolverError::NoSolution)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        Phase, SolverError, SolverOptions, State, compounds,
+        eos::{AlphaFunction, IdealGas, PengRobinson},
+    };
+    use float_eq::assert_float_eq;
+
+    /// Saturation pressure of water (T in K, P in Pa), from the steam tables
+    const WATER_PSAT: &[(f64, f64)] = &[
+        (323.15, 12352.0),
+        (373.15, 101418.0),
+        (423.15, 476160.0),
+        (473.15, 1554900.0),
+        (523.15, 3976200.0),
+        (573.15, 8587900.0),
+        (623.15, 16529000.0),
+    ];
+
+    fn mean_error(h2o: &crate::Molecule) -> f64 {
+        let sum: f64 = WATER_PSAT
+            .iter()
+            .map(|&(t, psat)| {
+                let p = h2o.saturation_pressure::<PengRobinson>(t).unwrap();
+                ((p - psat) / psat).abs()
+            })
+            .sum();
+        sum / WATER_PSAT.len() as f64
+    }
+
+    #[test]
+    fn water_saturation_pressure() {
+        let h2o = compounds::H2O;
+        let p = h2o.saturation_pressure::<PengRobinson>(373.15).unwrap();
+        assert_float_eq!(p, 101418.0, r2nd <= 0.1);
+
+        assert_eq!(h2o.saturation_pressure::<PengRobinson>(700.0), None);
+        assert_eq!(h2o.saturation_pressure::<IdealGas>(373.15), None);
+    }
+
+    #[test]
+    fn saturation_solver_options() {
+        let co2 = compounds::CO2;
+        let t = 280.0;
+        let psat = co2.saturation_pressure::<PengRobinson>(t).unwrap();
+
+        let options = SolverOptions {
+            max_iter: 2,
+            tol: 0.0,
+        };
+        let res = co2.saturation_pressure_with_options::<PengRobinson>(t, &options);
+        assert_eq!(res, Err(SolverError::DidNotConverge { iterations: 2 }));
+
+        let options = SolverOptions {
+            max_iter: 100,
+            tol: 1e-4,
+        };
+        let p = co2.saturation_pressure_with_options::<PengRobinson>(t, &options).unwrap();
+        assert_float_eq!(p, psat, r2nd <= 1e-3);
+
+        let res = co2.saturation_pressure_with_options::<PengRobinson>(350.0, &SolverOptions::default());
+        assert_eq!(res, Err(SolverError::NoSolution));
+    }
+
+    #[test]
+    fn maxwell_construction_matches_fugacity() {
+        let c3h8 = compounds::C3H8;
+        for t in [150.0, 200.0, 250.0, 300.0, 350.0] {
+            let psat = c3h8.saturation_pressure::<PengRobinson>(t).unwrap();
+            let maxwell = c3h8.saturation_pressure_maxwell::<PengRobinson>(t).unwrap();
+            assert_float_eq!(maxwell, psat, r2nd <= 1e-9);
+        }
+        // 9.97 bar at 300 K
+        assert_float_eq!(c3h8.saturation_pressure_maxwell::<PengRobinson>(300.0).unwrap(), 9.97e5, r2nd <= 0.03);
+
+        assert_eq!(c3h8.saturation_pressure_maxwell::<PengRobinson>(400.0), None);
+        assert_eq!(c3h8.saturation_pressure_maxwell::<IdealGas>(300.0), None);
+
+        let options = SolverOptions {
+            max_iter: 2,
+            tol: 0.0,
+        };
+        let res = c3h8.saturation_pressure_maxwell_with_options::<PengRobinson>(300.0, &options);
+        assert_eq!(res, Err(SolverError::DidNotConverge { iterations: 2 }));
+        let res = c3h8.saturation_pressure_maxwell_with_options::<PengRobinson>(400.0, &SolverOptions::default());
+        assert_eq!(res, Err(SolverError::NoSolution));
+    }
+
+    #[test]
+    fn water_enthalpy_of_vaporization() {
+        let h2o = compounds::H2O;
+        // 40.65 kJ/mol at 373.15 K
+        let dh = h2o.enthalpy_of_vaporization::<PengRobinson>(373.15).unwrap();
+        assert_float_eq!(dh, 40.65e3, r2nd <= 0.05);
+
+        // decreases to zero at the critical point
+        let dh_hot = h2o.enthalpy_of_vaporization::<PengRobinson>(600.0).unwrap();
+        assert!(dh_hot < dh);
+        assert_eq!(h2o.enthalpy_of_vaporization::<PengRobinson>(700.0), None);
+    }
+
+    #[test]
+    fn propane_costald_density() {
+        let c3h8 = compounds::C3H8;
+        // 489.6 kg/m3 at 300 K
+        let vm = c3h8.saturated_liquid_volume(300.0).unwrap();
+        assert_float_eq!(c3h8.molar_mass() / vm, 489.6, r2nd <= 0.03);
+
+        // the liquid root of Peng-Robinson is less accurate
+        let psat = c3h8.saturation_pressure::<PengRobinson>(300.0).unwrap();
+        let pr = c3h8.molar_volume_bracketed::<PengRobinson>(psat, 300.0, Phase::Liquid).unwrap();
+        assert!((c3h8.molar_mass() / pr - 489.6).abs() > (c3h8.molar_mass() / vm - 489.6).abs());
+
+        assert_eq!(c3h8.saturated_liquid_volume(370.0), None);
+    }
+
+    #[test]
+    fn mathias_copeman_improves_water_saturation_pressure() {
+        // coefficients fitted for Peng-Robinson
+        let mc = compounds::H2O.with_alpha(AlphaFunction::MathiasCopeman {
+            c1: 0.9130,
+            c2: -0.3395,
+            c3: 0.4223,
+        });
+        let standard = mean_error(&compounds::H2O);
+        let improved = mean_error(&mc);
+        assert!(improved < standard);
+        assert!(improved < 0.01);
+    }
+}
@@ -0,0 +1,210 @@
+use core::fmt::Write;
+
+#[cfg(all(not(feature = "std"), not(test)))]
+use crate::math::Float;
+#[cfg(not(feature = "std"))]
+use alloc::string::{String, ToString};
+
+use crate::{Gas, StateEos, eos::Eos};
+
+/// The state of a gas resolved at given pressure and temperature.
+/// Created with [`State::snapshot`](crate::State::snapshot).
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct StateSnapshot {
+    /// Pressure in Pa
+    pub p: f64,
+    /// Temperature in K
+    pub t: f64,
+    /// Compression factor Z
+    pub z: f64,
+    /// Molar volume in m^3/mol
+    pub molar_volume: f64,
+    /// Specific mass in kg/m^3
+    pub specific_mass: f64,
+    /// Enthalpy departure H - H_ig, in J/mol
+    pub enthalpy_departure: f64,
+    /// Entropy departure S - S_ig, in J/mol.K
+    pub entropy_departure: f64,
+    /// Molar isobaric heat capacity in J/mol.K, if the ideal gas heat capacity is known
+    pub cp: Option<f64>,
+}
+
+impl StateSnapshot {
+    /// Format the state on multiple lines with SI units (Pa, K, m³/mol, J/mol),
+    /// rounded to 4 significant figures
+    pub fn display_si(&self) -> String {
+        let mut s = String::new();
+        let _ = writeln!(s, "P = {} Pa", significant(self.p));
+        let _ = writeln!(s, "T = {} K", significant(self.t));
+        self.write_common(&mut s, self.molar_volume, "m³/mol", 1.0, "J/mol");
+        s
+    }
+
+    /// Format the state on multiple lines with engineering units (bar, °C, L/mol, kJ/mol),
+    /// rounded to 4 significant figures
+    pub fn display_engineering(&self) -> String {
+        let mut s = String::new();
+        let _ = writeln!(s, "P = {} bar", significant(self.p / 1e5));
+        let _ = writeln!(s, "T = {} °C", significant(self.t - 273.15));
+        self.write_common(&mut s, self.molar_volume * 1e3, "L/mol", 1e-3, "kJ/mol");
+        s
+    }
+
+    fn write_common(&self, s: &mut String, vm: f64, vm_unit: &str, h_factor: f64, h_unit: &str) {
+        let _ = writeln!(s, "Z = {}", significant(self.z));
+        let _ = writeln!(s, "Vm = {} {vm_unit}", significant(vm));
+        let _ = writeln!(s, "ρ = {} kg/m³", significant(self.specific_mass));
+        let _ = writeln!(s, "H - Hig = {} {h_unit}", significant(self.enthalpy_departure * h_factor));
+        let _ = writeln!(s, "S - Sig = {} J/mol.K", significant(self.entropy_departure));
+        if let Some(cp) = self.cp {
+            let _ = writeln!(s, "Cp = {} J/mol.K", significant(cp));
+        }
+    }
+}
+
+/// A computed state bundled with its inputs: the gas, the equation of state, the pressure and the temperature.
+///
+/// With the `serde` feature, the record can be serialized to and deserialized from any serde format.
+/// The gas is stored with its [`Display`](core::fmt::Display) form, that can be parsed back
+/// with [`Gas::from_str`](core::str::FromStr::from_str) for the built-in compounds and their mixtures.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GasStateRecord {
+    /// The gas, as displayed
+    pub gas: String,
+    /// The equation of state used
+    pub eos: Eos,
+    /// The inputs and computed outputs
+    pub state: StateSnapshot,
+}
+
+impl GasStateRecord {
+    /// Compute the state of `gas` with `eos` at `p` (in Pa) and `t` (in K)
+    ///
+    /// # Panics
+    /// Panics if the equation of state has no root, like [`State::snapshot`](crate::State::snapshot).
+    pub fn compute(gas: &Gas, eos: Eos, p: f64, t: f64) -> Self {
+        GasStateRecord {
+            gas: gas.to_string(),
+            eos,
+            state: gas.snapshot_eos(eos, p, t),
+        }
+    }
+}
+
+/// Format `v` with 4 significant figures, in scientific notation if it is very small or large
+fn significant(v: f64) -> String {
+    const DIGITS: i32 = 4;
+    if v == 0.0 || !v.is_finite() {
+        return alloc::format!("{v}");
+    }
+    let mag = v.abs().log10().floor() as i32;
+    if !(-3..6).contains(&mag) {
+        alloc::format!("{:.*e}", (DIGITS - 1) as usize, v)
+    } else {
+        alloc::format!("{:.*}", (DIGITS - 1 - mag).max(0) as usize, v)
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::GasStateRecord;
+    use crate::{
+        Gas, R, State, compounds,
+        eos::{Eos, IdealGas, PengRobinson},
+    };
+    use float_eq::assert_float_eq;
+
+    #[test]
+    fn snapshot_matches_direct_calls() {
+        let ng = compounds::natural_gas();
+        let snap = ng.snapshot::<PengRobinson>(100e5, 280.0);
+        assert_eq!(snap.z, ng.z::<PengRobinson>(100e5, 280.0));
+        assert_float_eq!(
+            snap.specific_mass,
+            ng.specific_mass::<PengRobinson>(100e5, 280.0),
+            r2nd <= 1e-12
+        );
+        assert_eq!(
+            snap.enthalpy_departure,
+            ng.enthalpy_departure::<PengRobinson>(100e5, 280.0)
+        );
+        assert_eq!(snap.cp, ng.isobaric_heat_capacity::<PengRobinson>(100e5, 280.0));
+        assert_eq!(compounds::BR2.snapshot::<PengRobinson>(1e5, 400.0).cp, None);
+    }
+
+    #[test]
+    fn display() {
+        let n2 = compounds::N2;
+        let snap = n2.snapshot::<PengRobinson>(200e5, 300.0);
+
+        let si = snap.display_si();
+        assert!(si.contains("P = 2.000e7 Pa\n"));
+        assert!(si.contains("T = 300.0 K\n"));
+        assert!(si.contains("Z = 1.029\n"));
+        assert!(si.contains("Vm = 1.283e-4 m³/mol\n"));
+        assert!(si.contains("ρ = 218.3 kg/m³\n"));
+        assert!(si.contains("H - Hig = -1015 J/mol\n"));
+
+        let eng = snap.display_engineering();
+        assert!(eng.contains("P = 200.0 bar\n"));
+        assert!(eng.contains("T = 26.85 °C\n"));
+        assert!(eng.contains("Vm = 0.1283 L/mol\n"));
+        assert!(eng.contains("ρ = 218.3 kg/m³\n"));
+        assert!(eng.contains("H - Hig = -1.015 kJ/mol\n"));
+        assert!(eng.contains("Cp = 36.65 J/mol.K\n"));
+        assert_eq!(eng.lines().count(), 8);
+
+        let snap = compounds::BR2.snapshot::<PengRobinson>(1e5, 400.0);
+        assert!(!snap.display_engineering().contains("Cp"));
+    }
+
+    #[test]
+    fn record() {
+        let co2: Gas = compounds::CO2.into();
+        let record = GasStateRecord::compute(&co2, Eos::PengRobinson, 50e5, 320.0);
+        assert_eq!(record.gas, "CO2");
+        assert_eq!(record.gas.parse::<Gas>().unwrap(), co2);
+        assert_eq!(record.state, co2.snapshot::<PengRobinson>(50e5, 320.0));
+        assert!(record.state.cp.is_some());
+
+        let record = GasStateRecord::compute(&compounds::BR2.into(), Eos::SoaveRedlichKwong, 1e5, 400.0);
+        assert_eq!(record.state.cp, None);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn record_serde_round_trip() {
+        let ng: Gas = compounds::natural_gas().into();
+        for eos in [Eos::PengRobinson, Eos::LeeKesler] {
+            let record = GasStateRecord::compute(&ng, eos, 70e5, 280.0);
+            let json = serde_json::to_string(&record).unwrap();
+            let parsed: GasStateRecord = serde_json::from_str(&json).unwrap();
+            assert_eq!(parsed, record);
+        }
+
+        // without heat capacity
+        let record = GasStateRecord::compute(&compounds::BR2.into(), Eos::SoaveRedlichKwong, 1e5, 400.0);
+        let json = serde_json::to_string(&record).unwrap();
+        assert!(json.contains("\"cp\":null"));
+        assert_eq!(serde_json::from_str::<GasStateRecord>(&json).unwrap(), record);
+    }
+
+    #[test]
+    fn departures() {
+        // residual Gibbs energy H - TS is RT·ln(φ)
+        let n2 = compounds::N2;
+        let (p, t) = (200e5, 200.0);
+        let snap = n2.snapshot::<PengRobinson>(p, t);
+        let g_res = snap.enthalpy_departure - t * snap.entropy_departure;
+        let ln_phi = n2.fugacity_coefficient::<PengRobinson>(p, t).ln();
+        assert_float_eq!(g_res, R * t * ln_phi, r2nd <= 1e-6);
+        assert!(snap.enthalpy_departure < 0.0);
+        assert!(snap.entropy_departure < 0.0);
+
+        let snap = n2.snapshot::<IdealGas>(p, t);
+        assert_eq!(snap.enthalpy_departure, 0.0);
+        assert_eq!(snap.entropy_departure, 0.0);
+    }
+}
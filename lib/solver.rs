@@ -0,0 +1,62 @@
+use core::fmt;
+
+use crate::math;
+
+/// Convergence control of the iterative solvers,
+/// such as [`Molecule::saturation_pressure_with_options`](crate::Molecule::saturation_pressure_with_options)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SolverOptions {
+    /// The maximum number of iterations
+    pub max_iter: usize,
+    /// The tolerance on the solution, relative to its magnitude
+    pub tol: f64,
+}
+
+impl Default for SolverOptions {
+    fn default() -> Self {
+        SolverOptions {
+            max_iter: 100,
+            tol: 1e-12,
+        }
+    }
+}
+
+/// An error of an iterative solver
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SolverError {
+    /// There is no solution, or it could not be bracketed in the searched range
+    NoSolution,
+    /// The solution was bracketed, but the tolerance was not reached within the maximum number of iterations
+    DidNotConverge { iterations: usize },
+}
+
+impl fmt::Display for SolverError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SolverError::NoSolution => write!(f, "No solution found"),
+            SolverError::DidNotConverge { iterations } => {
+                write!(f, "The solver did not converge after {iterations} iterations")
+            }
+        }
+    }
+}
+
+impl core::error::Error for SolverError {}
+
+/// Find a root of `f` between `a` and `b` with the Brent method,
+/// to the tolerance of `options` relative to `scale`
+pub(crate) fn find_root_brent<F>(a: f64, b: f64, f: F, scale: f64, options: &SolverOptions) -> Result<f64, SolverError>
+where
+    F: Fn(f64) -> f64,
+{
+    let mut convergency = math::SimpleConvergency {
+        eps: options.tol * scale.abs(),
+        max_iter: options.max_iter,
+    };
+    math::find_root_brent(a, b, f, &mut convergency).map_err(|err| match err {
+        math::SearchError::NoConvergency => SolverError::DidNotConverge {
+            iterations: options.max_iter,
+        },
+        _ => SolverError::NoSolution,
+    })
+}
@@ -0,0 +1,117 @@
+use core::ops::Range;
+#[cfg(not(feature = "std"))]
+use alloc::{vec, vec::Vec};
+
+use crate::{Comp, Mixture, Molecule, State, eos::EquationOfState};
+
+/// A sweep of pressures along an isotherm, to compute the compression factor of a gas.
+///
+/// This is the data behind Z vs. pressure charts.
+#[derive(Debug, Clone, PartialEq)]
+pub struct IsothermSweep {
+    t: f64,
+    pressures: Vec<f64>,
+}
+
+impl IsothermSweep {
+    /// A sweep at temperature `t` (in K) of `steps` pressures evenly spaced
+    /// over `pressures` (in Pa), both ends included.
+    pub fn linear(t: f64, pressures: Range<f64>, steps: usize) -> Self {
+        let pressures = match steps {
+            0 => vec![],
+            1 => vec![pressures.start],
+            _ => {
+                let dp = (pressures.end - pressures.start) / (steps - 1) as f64;
+                (0..steps)
+                    .map(|i| pressures.start + i as f64 * dp)
+                    .collect()
+            }
+        };
+        IsothermSweep { t, pressures }
+    }
+
+    /// A sweep at temperature `t` (in K) of the given pressures (in Pa)
+    pub fn with_pressures(t: f64, pressures: &[f64]) -> Self {
+        IsothermSweep {
+            t,
+            pressures: pressures.to_vec(),
+        }
+    }
+
+    /// The temperature of the sweep, in K
+    pub fn temperature(&self) -> f64 {
+        self.t
+    }
+
+    /// The pressures of the sweep, in Pa
+    pub fn pressures(&self) -> &[f64] {
+        &self.pressures
+    }
+
+    /// Compute the `(pressure, z)` pairs of `gas` along the sweep
+    pub fn run<E: EquationOfState, S: State>(&self, gas: &S) -> Vec<(f64, f64)> {
+        let state = gas.prepare::<E>(self.t);
+        self.pressures.iter().map(|&p| (p, state.z(p))).collect()
+    }
+}
+
+/// Compute the `(x_a, z)` pairs of the binary mixtures of `a` and `b` at `p` (in Pa) and `t` (in K),
+/// for `n` molar fractions `x_a` of `a` evenly spaced from 0 to 1, both ends included.
+///
+/// The ends are the pure molecules `b` and `a`.
+pub fn composition_sweep<E: EquationOfState>(a: &Molecule, b: &Molecule, p: f64, t: f64, n: usize) -> Vec<(f64, f64)> {
+    let fractions = match n {
+        0 => vec![],
+        1 => vec![0.0],
+        _ => (0..n).map(|i| i as f64 / (n - 1) as f64).collect(),
+    };
+    fractions
+        .into_iter()
+        .map(|x| {
+            let z = if x <= 0.0 {
+                b.z::<E>(p, t)
+            } else if x >= 1.0 {
+                a.z::<E>(p, t)
+            } else {
+                Mixture::new([Comp::Factor(x, (*a).into()), Comp::Remainder((*b).into())])
+                    .expect("Should build a binary mixture")
+                    .z::<E>(p, t)
+            };
+            (x, z)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{IsothermSweep, composition_sweep};
+    use crate::{State, compounds, eos::PengRobinson};
+
+    #[test]
+    fn sweep_is_monotonic_and_finite() {
+        let n2 = compounds::N2;
+        let sweep = IsothermSweep::linear(300.0, 1e5..1000e5, 100);
+        let points = sweep.run::<PengRobinson, _>(&n2);
+
+        assert_eq!(points.len(), 100);
+        assert_eq!(points[0].0, 1e5);
+        assert_eq!(points[99].0, 1000e5);
+        assert!(points.windows(2).all(|w| w[0].0 < w[1].0));
+        assert!(points.iter().all(|(_, z)| z.is_finite()));
+        assert_eq!(points[42].1, n2.z::<PengRobinson>(points[42].0, 300.0));
+    }
+
+    #[test]
+    fn composition_sweep_ends_are_pure() {
+        let (co2, n2) = (compounds::CO2, compounds::N2);
+        let (p, t) = (50e5, 300.0);
+        let points = composition_sweep::<PengRobinson>(&co2, &n2, p, t, 11);
+
+        assert_eq!(points.len(), 11);
+        assert_eq!(points[0], (0.0, n2.z::<PengRobinson>(p, t)));
+        assert_eq!(points[10], (1.0, co2.z::<PengRobinson>(p, t)));
+        assert!((points[5].0 - 0.5).abs() < 1e-15);
+        // CO2 is much less ideal than N2
+        assert!(points.windows(2).all(|w| w[1].1 < w[0].1));
+    }
+}
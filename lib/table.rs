@@ -0,0 +1,141 @@
+use core::ops::Range;
+#[cfg(all(not(feature = "std"), not(test)))]
+use crate::math::Float;
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+
+use crate::{State, eos::EquationOfState};
+
+/// A table of compression factors precomputed over a pressure and temperature window,
+/// for fast lookups by bilinear interpolation.
+///
+/// The interpolation error scales with the square of the grid spacing:
+/// it is about `(Δp²·|∂²Z/∂p²| + ΔT²·|∂²Z/∂T²|) / 8`,
+/// so doubling the resolution divides the error by about 4.
+/// For N2 with Peng-Robinson over 1 to 200 bar and 200 to 400 K,
+/// a resolution of 20 gives errors up to 6e-4, and 50 up to 1e-4.
+/// Across a phase boundary Z is discontinuous and the error doesn't decrease with the resolution.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ZTable {
+    pressures: Range<f64>,
+    temperatures: Range<f64>,
+    resolution: usize,
+    /// Z values, one row of `resolution` pressures per temperature
+    z: Vec<f64>,
+}
+
+impl ZTable {
+    /// Build a table of the Z values of `gas` for `resolution` pressures (in Pa)
+    /// and `resolution` temperatures (in K) evenly spaced over the ranges, both ends included.
+    /// A resolution below 2 is raised to 2.
+    ///
+    /// # Panics
+    /// Panics if the equation of state has no root within the ranges, like [`State::z`].
+    pub fn build<E: EquationOfState, S: State>(
+        gas: &S,
+        pressures: Range<f64>,
+        temperatures: Range<f64>,
+        resolution: usize,
+    ) -> Self {
+        let resolution = resolution.max(2);
+        let mut table = ZTable {
+            pressures,
+            temperatures,
+            resolution,
+            z: Vec::with_capacity(resolution * resolution),
+        };
+        for it in 0..resolution {
+            let state = gas.prepare::<E>(table.temperature(it));
+            for ip in 0..resolution {
+                table.z.push(state.z(table.pressure(ip)));
+            }
+        }
+        table
+    }
+
+    /// The pressure range of the table, in Pa
+    pub fn pressures(&self) -> Range<f64> {
+        self.pressures.clone()
+    }
+
+    /// The temperature range of the table, in K
+    pub fn temperatures(&self) -> Range<f64> {
+        self.temperatures.clone()
+    }
+
+    /// The number of points along each axis of the table
+    pub fn resolution(&self) -> usize {
+        self.resolution
+    }
+
+    /// The compression factor at `p` (in Pa) and `t` (in K), interpolated in the table.
+    ///
+    /// Outside of the table ranges, Z is extrapolated linearly from the edge cells.
+    pub fn get(&self, p: f64, t: f64) -> f64 {
+        let (ip, fp) = Self::locate(p, &self.pressures, self.resolution);
+        let (it, ft) = Self::locate(t, &self.temperatures, self.resolution);
+        let z = |it: usize, ip: usize| self.z[it * self.resolution + ip];
+
+        let lo = z(it, ip) + fp * (z(it, ip + 1) - z(it, ip));
+        let hi = z(it + 1, ip) + fp * (z(it + 1, ip + 1) - z(it + 1, ip));
+        lo + ft * (hi - lo)
+    }
+
+    fn pressure(&self, idx: usize) -> f64 {
+        Self::node(&self.pressures, self.resolution, idx)
+    }
+
+    fn temperature(&self, idx: usize) -> f64 {
+        Self::node(&self.temperatures, self.resolution, idx)
+    }
+
+    fn node(range: &Range<f64>, resolution: usize, idx: usize) -> f64 {
+        range.start + idx as f64 * (range.end - range.start) / (resolution - 1) as f64
+    }
+
+    /// The index of the cell containing `x`, and the position of `x` within the cell
+    fn locate(x: f64, range: &Range<f64>, resolution: usize) -> (usize, f64) {
+        let pos = (x - range.start) / (range.end - range.start) * (resolution - 1) as f64;
+        let idx = (pos.floor().max(0.0) as usize).min(resolution - 2);
+        (idx, pos - idx as f64)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ZTable;
+    use crate::{State, compounds, eos::PengRobinson};
+    use float_eq::assert_float_eq;
+
+    /// The largest error of the table, at the centers of its cells
+    fn max_error(table: &ZTable) -> f64 {
+        let n = table.resolution();
+        let (ps, ts) = (table.pressures(), table.temperatures());
+        let (dp, dt) = ((ps.end - ps.start) / (n - 1) as f64, (ts.end - ts.start) / (n - 1) as f64);
+        let mut err: f64 = 0.0;
+        for it in 0..n - 1 {
+            for ip in 0..n - 1 {
+                let (p, t) = (ps.start + (ip as f64 + 0.5) * dp, ts.start + (it as f64 + 0.5) * dt);
+                err = err.max((table.get(p, t) - compounds::N2.z::<PengRobinson>(p, t)).abs());
+            }
+        }
+        err
+    }
+
+    #[test]
+    fn lookups_match_exact_z() {
+        let n2 = compounds::N2;
+        let coarse = ZTable::build::<PengRobinson, _>(&n2, 1e5..200e5, 200.0..400.0, 20);
+        let fine = ZTable::build::<PengRobinson, _>(&n2, 1e5..200e5, 200.0..400.0, 50);
+
+        // exact on the nodes
+        assert_float_eq!(coarse.get(1e5, 200.0), n2.z::<PengRobinson>(1e5, 200.0), abs <= 1e-15);
+        assert_float_eq!(coarse.get(200e5, 400.0), n2.z::<PengRobinson>(200e5, 400.0), abs <= 1e-15);
+
+        // the error decreases with the square of the spacing
+        let (coarse_err, fine_err) = (max_error(&coarse), max_error(&fine));
+        assert!(coarse_err < 1e-3, "{coarse_err}");
+        assert!(fine_err < 2e-4, "{fine_err}");
+        assert!(fine_err < coarse_err / 4.0);
+    }
+}
@@ -0,0 +1,331 @@
+//! Transport properties of gases.
+
+use core::f64::consts::FRAC_2_PI;
+
+use crate::{Gas, Mixture, Molecule, Pvt, R, State, eos::DefaultEos};
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+#[cfg(all(not(feature = "std"), not(test)))]
+use crate::math::Float;
+
+impl Molecule {
+    /// Estimate the dynamic viscosity at `p` (in Pa) and `t` (in K), in Pa.s.
+    ///
+    /// Uses the corresponding states method of Lucas (1980), with the corrections
+    /// for polar molecules (see [`Molecule::dipole`]) and for high pressures.
+    /// The method applies to gases, the quantum correction of hydrogen and helium is not applied.
+    /// The error is typically within a few percent at low pressure.
+    /// Returns NaN in the liquid region, that is below the critical temperature
+    /// and above the saturation pressure of [`DefaultEos`].
+    pub fn viscosity(&self, p: f64, t: f64) -> f64 {
+        if self.is_liquid(p, t) {
+            return f64::NAN;
+        }
+        let cs = &self.critical_state;
+        let (tr, pr) = (t / cs.t, p / cs.p);
+        let (xi, fp0, z1) = self.lucas_low_pressure(tr);
+        z1 / xi * 1e-7 * lucas_pressure_factor(tr, pr, fp0, z1)
+    }
+
+    /// Estimate the thermal conductivity at `p` (in Pa) and `t` (in K), in W/m.K.
+    ///
+    /// The conductivity of the dilute gas is computed with the corresponding states method
+    /// of Chung et al. (1984), from the low pressure viscosity of Lucas and the ideal gas
+    /// heat capacity. The dense gas contribution is added with the correlation of Stiel and Thodos (1964),
+    /// with the molar volume of [`DefaultEos`].
+    /// Returns `None` if the ideal gas heat capacity is unknown (see [`Molecule::cp`]),
+    /// and NaN in the liquid region, like [`Molecule::viscosity`].
+    pub fn thermal_conductivity(&self, p: f64, t: f64) -> Option<f64> {
+        let lambda0 = self.low_pressure_conductivity(t)?;
+        if self.is_liquid(p, t) {
+            return Some(f64::NAN);
+        }
+        let cs = &self.critical_state;
+        let rho_r = cs.v / self.molar_volume::<DefaultEos>(p, t);
+        Some(lambda0 + stiel_thodos(rho_r, cs, self.m))
+    }
+
+    /// The thermal conductivity of the dilute gas at the temperature `t` with the method of Chung, in W/m.K
+    fn low_pressure_conductivity(&self, t: f64) -> Option<f64> {
+        let cv = self.ideal_cp(t)? - R;
+        let tr = t / self.critical_state.t;
+        let eta0 = self.low_pressure_viscosity(t);
+
+        let w = self.acentric_factor();
+        let alpha = cv / R - 1.5;
+        let beta = 0.7862 - 0.7109 * w + 1.3168 * w * w;
+        let z = 2.0 + 10.5 * tr * tr;
+        let psi = 1.0
+            + alpha * (0.215 + 0.28288 * alpha - 1.061 * beta + 0.26665 * z)
+                / (FRAC_2_PI + beta * z + 1.061 * alpha * beta);
+        Some(3.75 * psi * eta0 * R / self.m)
+    }
+
+    /// Whether the molecule is liquid at `p` and `t`, according to [`DefaultEos`]
+    fn is_liquid(&self, p: f64, t: f64) -> bool {
+        t <= self.critical_state.t && self.saturation_pressure::<DefaultEos>(t).is_some_and(|psat| p > psat)
+    }
+
+    /// The inverse reduced viscosity ξ (in 1/µP), the polarity correction
+    /// and the reduced viscosity ηξ at low pressure of the method of Lucas
+    fn lucas_low_pressure(&self, tr: f64) -> (f64, f64, f64) {
+        let cs = &self.critical_state;
+        // the correlation uses M in g/mol, Pc in bar and viscosities in µP
+        let (m, pc) = (self.m * 1e3, cs.p * 1e-5);
+
+        let xi = 0.176 * (cs.t / (m.powi(3) * pc.powi(4))).powf(1.0 / 6.0);
+
+        let mu_r = 52.46 * self.dipole * self.dipole * pc / (cs.t * cs.t);
+        let zc = self.critical_compressibility();
+        let fp0 = if mu_r < 0.022 {
+            1.0
+        } else if mu_r < 0.075 {
+            1.0 + 30.55 * (0.292 - zc).max(0.0).powf(1.72)
+        } else {
+            1.0 + 30.55 * (0.292 - zc).max(0.0).powf(1.72) * (0.96 + 0.1 * (tr - 0.7)).abs()
+        };
+
+        (xi, fp0, lucas_z1(tr) * fp0)
+    }
+
+    /// The viscosity of the dilute gas at the temperature `t`, in Pa.s
+    fn low_pressure_viscosity(&self, t: f64) -> f64 {
+        let (xi, _, z1) = self.lucas_low_pressure(t / self.critical_state.t);
+        z1 / xi * 1e-7
+    }
+}
+
+impl Mixture {
+    /// Estimate the dynamic viscosity at `p` (in Pa) and `t` (in K), in Pa.s.
+    ///
+    /// The low pressure viscosities of the components (see [`Molecule::viscosity`])
+    /// are combined with the mixing rule of Wilke (1950), then corrected for the pressure
+    /// with the method of Lucas at the pseudo-critical point of the mixture.
+    /// The mixture is assumed to be a gas: a component can be below its own saturation temperature.
+    pub fn viscosity(&self, p: f64, t: f64) -> f64 {
+        let comps: Vec<(f64, f64, f64, f64)> = self
+            .comps
+            .iter()
+            .map(|(f, m)| {
+                let eta = m.low_pressure_viscosity(t);
+                (*f, m.m, eta, eta)
+            })
+            .collect();
+        let eta0 = wassiljewa(&comps, 1.0);
+
+        let cs = self.transport_critical();
+        let (tr, pr) = (t / cs.t, p / cs.p);
+        let fp0: f64 = self
+            .comps
+            .iter()
+            .map(|(f, m)| f * m.lucas_low_pressure(t / m.critical_state.t).1)
+            .sum();
+        eta0 * lucas_pressure_factor(tr, pr, fp0, lucas_z1(tr) * fp0)
+    }
+
+    /// The pseudo-critical point of the corresponding states methods for transport properties:
+    /// the molar fraction weighted averages of Tc, Vc and Zc, with Pc = Zc·R·Tc/Vc
+    fn transport_critical(&self) -> Pvt {
+        let (mut tc, mut vc, mut zc) = (0.0, 0.0, 0.0);
+        for (f, m) in &self.comps {
+            tc += f * m.critical_state.t;
+            vc += f * m.critical_state.v;
+            zc += f * m.critical_compressibility();
+        }
+        Pvt {
+            p: zc * R * tc / vc,
+            v: vc,
+            t: tc,
+        }
+    }
+
+    /// Estimate the thermal conductivity at `p` (in Pa) and `t` (in K), in W/m.K.
+    ///
+    /// The low pressure conductivities of the components (see [`Molecule::thermal_conductivity`])
+    /// are combined with the mixing rule of Wassiljewa, with the coefficients of Mason and Saxena (1958).
+    /// The dense gas contribution of Stiel and Thodos is then added for the mixture as a whole,
+    /// at its pseudo-critical point and with its molar volume of [`DefaultEos`].
+    /// Returns `None` if the ideal gas heat capacity of a component is unknown.
+    pub fn thermal_conductivity(&self, p: f64, t: f64) -> Option<f64> {
+        let comps = self
+            .comps
+            .iter()
+            .map(|(f, m)| Some((*f, m.m, m.low_pressure_viscosity(t), m.low_pressure_conductivity(t)?)))
+            .collect::<Option<Vec<_>>>()?;
+        let lambda0 = wassiljewa(&comps, 1.065);
+
+        let cs = self.transport_critical();
+        let rho_r = cs.v / self.molar_volume::<DefaultEos>(p, t);
+        Some(lambda0 + stiel_thodos(rho_r, &cs, self.molar_mass()))
+    }
+}
+
+impl Gas {
+    /// Estimate the dynamic viscosity at `p` (in Pa) and `t` (in K), in Pa.s.
+    ///
+    /// See [`Molecule::viscosity`] and [`Mixture::viscosity`].
+    pub fn viscosity(&self, p: f64, t: f64) -> f64 {
+        match self {
+            Gas::Molecule(m) => m.viscosity(p, t),
+            Gas::Mixture(m) => m.viscosity(p, t),
+        }
+    }
+
+    /// Estimate the thermal conductivity at `p` (in Pa) and `t` (in K), in W/m.K.
+    ///
+    /// See [`Molecule::thermal_conductivity`] and [`Mixture::thermal_conductivity`].
+    pub fn thermal_conductivity(&self, p: f64, t: f64) -> Option<f64> {
+        match self {
+            Gas::Molecule(m) => m.thermal_conductivity(p, t),
+            Gas::Mixture(m) => m.thermal_conductivity(p, t),
+        }
+    }
+}
+
+/// The reduced viscosity ηξ at low pressure of the method of Lucas, without the polarity correction
+fn lucas_z1(tr: f64) -> f64 {
+    0.807 * tr.powf(0.618) - 0.357 * (-0.449 * tr).exp() + 0.340 * (-4.058 * tr).exp() + 0.018
+}
+
+/// The ratio of the viscosity at the reduced pressure `pr` to the low pressure viscosity,
+/// with the method of Lucas, where `fp0` is the polarity correction and `z1` the reduced
+/// viscosity at low pressure
+fn lucas_pressure_factor(tr: f64, pr: f64, fp0: f64, z1: f64) -> f64 {
+    let z2 = if tr <= 1.0 {
+        let alpha = 3.262 + 14.98 * pr.powf(5.508);
+        let beta = 1.390 + 5.746 * pr;
+        0.600 + 0.760 * pr.powf(alpha) + (6.990 * pr.powf(beta) - 0.6) * (1.0 - tr)
+    } else {
+        let a = 1.245e-3 / tr * (5.1726 * tr.powf(-0.3286)).exp();
+        let b = a * (1.6553 * tr - 1.2723);
+        let c = 0.4489 / tr * (3.0578 * tr.powf(-37.7332)).exp();
+        let d = 1.7368 / tr * (2.2310 * tr.powf(-7.6351)).exp();
+        let e = 1.3088;
+        let f = 0.9425 * (-0.1853 * tr.powf(0.4489)).exp();
+        z1 * (1.0 + a * pr.powf(e) / (b * pr.powf(f) + 1.0 / (1.0 + c * pr.powf(d))))
+    };
+    let y = z2 / z1;
+    let fp = (1.0 + (fp0 - 1.0) / y.powi(3)) / fp0;
+    y * fp
+}
+
+/// The dense gas excess conductivity of Stiel and Thodos (1964), in W/m.K,
+/// at the reduced density `rho_r`, for the critical point `cs` and the molar mass `m` (in kg/mol)
+fn stiel_thodos(rho_r: f64, cs: &Pvt, m: f64) -> f64 {
+    // the correlation uses Pc in bar and M in g/mol
+    let gamma = 210.0 * (cs.t * (m * 1e3).powi(3) / (cs.p * 1e-5).powi(4)).powf(1.0 / 6.0);
+    let excess = if rho_r < 0.5 {
+        1.22e-2 * ((0.535 * rho_r).exp() - 1.0)
+    } else if rho_r < 2.0 {
+        1.14e-2 * ((0.67 * rho_r).exp() - 1.069)
+    } else {
+        2.60e-3 * ((1.155 * rho_r).exp() + 2.016)
+    };
+    excess / (gamma * cs.z().powi(5))
+}
+
+/// Mix the property of components given as `(molar fraction, molar mass, viscosity, property)`
+/// with the rule of Wassiljewa, where the interaction coefficients are computed from the viscosities
+/// with the form of Wilke scaled by `eps` (1 gives the rule of Wilke for the viscosity).
+fn wassiljewa(comps: &[(f64, f64, f64, f64)], eps: f64) -> f64 {
+    comps
+        .iter()
+        .enumerate()
+        .map(|(i, &(yi, mi, etai, xi))| {
+            let denom: f64 = comps
+                .iter()
+                .enumerate()
+                .map(|(j, &(yj, mj, etaj, _))| {
+                    if i == j {
+                        return yj;
+                    }
+                    let num = 1.0 + (etai / etaj).sqrt() * (mj / mi).powf(0.25);
+                    eps * yj * num * num / (8.0 * (1.0 + mi / mj)).sqrt()
+                })
+                .sum();
+            yi * xi / denom
+        })
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::compounds;
+    use float_eq::assert_float_eq;
+
+    #[test]
+    fn viscosity_of_nitrogen() {
+        // 17.9 µPa.s at 300 K and 1 atm (NIST)
+        let eta = compounds::N2.viscosity(101325.0, 300.0);
+        assert_float_eq!(eta, 17.9e-6, rmax <= 0.02);
+
+        // increases with the pressure, and with the temperature in the gas phase
+        assert!(compounds::N2.viscosity(200e5, 300.0) > eta);
+        assert!(compounds::N2.viscosity(101325.0, 400.0) > eta);
+
+        // liquid
+        assert!(compounds::N2.viscosity(10e5, 80.0).is_nan());
+    }
+
+    #[test]
+    fn viscosity_of_polar_and_mixture() {
+        // water vapor: 13.3 µPa.s at 400 K and 1 bar (NIST)
+        assert_float_eq!(compounds::H2O.viscosity(1e5, 400.0), 13.3e-6, rmax <= 0.1);
+
+        // dry air: 18.5 µPa.s at 300 K and 1 atm
+        let air = compounds::dry_air();
+        assert_float_eq!(air.viscosity(101325.0, 300.0), 18.5e-6, rmax <= 0.03);
+    }
+
+    #[test]
+    fn viscosity_of_natural_gas_at_pipeline_pressure() {
+        // propane and butanes are below their saturation temperature at this pressure
+        let ng = compounds::natural_gas();
+        assert!(compounds::C4H10.viscosity(50e5, 288.15).is_nan());
+
+        // methane is about 10.9 µPa.s at 1 bar and 12.0 µPa.s at 50 bar at 288 K (NIST)
+        let low = ng.viscosity(1e5, 288.15);
+        let high = ng.viscosity(50e5, 288.15);
+        assert_float_eq!(low, 10.9e-6, rmax <= 0.05);
+        assert_float_eq!(high, 12.0e-6, rmax <= 0.08);
+        assert!(high > low);
+
+        // a single component mixture has the viscosity of the molecule
+        let pure = crate::Mixture::new([crate::Comp::Remainder(compounds::N2.into())]).unwrap();
+        assert_float_eq!(pure.viscosity(200e5, 300.0), compounds::N2.viscosity(200e5, 300.0), rmax <= 1e-12);
+    }
+
+    #[test]
+    fn thermal_conductivity_of_natural_gas_at_pipeline_pressure() {
+        let ng = compounds::natural_gas();
+        assert!(compounds::C4H10.thermal_conductivity(50e5, 288.15).unwrap().is_nan());
+
+        // methane is about 0.0333 W/m.K at 1 bar and 0.0365 W/m.K at 50 bar at 288 K (NIST)
+        let low = ng.thermal_conductivity(1e5, 288.15).unwrap();
+        let high = ng.thermal_conductivity(50e5, 288.15).unwrap();
+        assert_float_eq!(low, 0.0333, rmax <= 0.12);
+        assert_float_eq!(high, 0.0365, rmax <= 0.12);
+        assert!(high > low);
+
+        // a single component mixture has the conductivity of the molecule
+        let pure = crate::Mixture::new([crate::Comp::Remainder(compounds::N2.into())]).unwrap();
+        let lambda = compounds::N2.thermal_conductivity(200e5, 300.0).unwrap();
+        assert_float_eq!(pure.thermal_conductivity(200e5, 300.0).unwrap(), lambda, rmax <= 1e-12);
+    }
+
+    #[test]
+    fn thermal_conductivity_of_air() {
+        // 0.0263 W/m.K at 300 K and 1 atm
+        let air = compounds::dry_air();
+        let lambda = air.thermal_conductivity(101325.0, 300.0).unwrap();
+        assert_float_eq!(lambda, 0.0263, rmax <= 0.04);
+
+        // 0.0259 W/m.K for nitrogen (NIST), increasing with the pressure
+        let lambda = compounds::N2.thermal_conductivity(101325.0, 300.0).unwrap();
+        assert_float_eq!(lambda, 0.0259, rmax <= 0.04);
+        assert!(compounds::N2.thermal_conductivity(200e5, 300.0).unwrap() > lambda);
+
+        // no heat capacity data
+        assert_eq!(compounds::BR2.thermal_conductivity(1e5, 600.0), None);
+    }
+}
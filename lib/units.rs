@@ -0,0 +1,148 @@
+//! Unit-typed quantities.
+//!
+//! The raw `f64` API of this crate is expressed in SI units.
+//! These types make the unit explicit at construction and convert to SI internally.
+
+/// A pressure, stored in Pa
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+pub struct Pressure(f64);
+
+impl Pressure {
+    /// A pressure in Pa
+    pub const fn pascal(p: f64) -> Self {
+        Pressure(p)
+    }
+
+    /// A pressure in kPa
+    pub const fn kilopascal(p: f64) -> Self {
+        Pressure(p * 1e3)
+    }
+
+    /// A pressure in MPa
+    pub const fn megapascal(p: f64) -> Self {
+        Pressure(p * 1e6)
+    }
+
+    /// A pressure in bar
+    pub const fn bar(p: f64) -> Self {
+        Pressure(p * 1e5)
+    }
+
+    /// A pressure in standard atmospheres
+    pub const fn atm(p: f64) -> Self {
+        Pressure(p * 101325.0)
+    }
+
+    /// A pressure in pounds per square inch
+    pub const fn psi(p: f64) -> Self {
+        Pressure(p * 6894.757293168)
+    }
+
+    /// The pressure in Pa
+    pub const fn to_pascal(self) -> f64 {
+        self.0
+    }
+
+    /// The pressure in bar
+    pub const fn to_bar(self) -> f64 {
+        self.0 / 1e5
+    }
+}
+
+/// A temperature, stored in K
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+pub struct Temperature(f64);
+
+impl Temperature {
+    /// A temperature in K
+    pub const fn kelvin(t: f64) -> Self {
+        Temperature(t)
+    }
+
+    /// A temperature in °C
+    pub const fn celsius(t: f64) -> Self {
+        Temperature(t + 273.15)
+    }
+
+    /// A temperature in °F
+    pub const fn fahrenheit(t: f64) -> Self {
+        Temperature((t - 32.0) * 5.0 / 9.0 + 273.15)
+    }
+
+    /// The temperature in K
+    pub const fn to_kelvin(self) -> f64 {
+        self.0
+    }
+
+    /// The temperature in °C
+    pub const fn to_celsius(self) -> f64 {
+        self.0 - 273.15
+    }
+}
+
+/// A molar volume, stored in m^3/mol
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+pub struct MolarVolume(f64);
+
+impl MolarVolume {
+    /// A molar volume in m^3/mol
+    pub const fn cubic_meter_per_mol(vm: f64) -> Self {
+        MolarVolume(vm)
+    }
+
+    /// A molar volume in L/mol
+    pub const fn liter_per_mol(vm: f64) -> Self {
+        MolarVolume(vm * 1e-3)
+    }
+
+    /// The molar volume in m^3/mol
+    pub const fn to_cubic_meter_per_mol(self) -> f64 {
+        self.0
+    }
+
+    /// The molar volume in L/mol
+    pub const fn to_liter_per_mol(self) -> f64 {
+        self.0 * 1e3
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{State, compounds, eos::PengRobinson};
+    use float_eq::assert_float_eq;
+
+    #[test]
+    fn conversions() {
+        assert_float_eq!(Pressure::bar(1.0).to_pascal(), 1e5, ulps <= 1);
+        assert_float_eq!(Pressure::psi(14.6959488).to_pascal(), 101325.0, r2nd <= 1e-8);
+        assert_float_eq!(Pressure::atm(1.0).to_bar(), 1.01325, ulps <= 1);
+        assert_float_eq!(Temperature::celsius(25.0).to_kelvin(), 298.15, ulps <= 1);
+        assert_float_eq!(Temperature::fahrenheit(212.0).to_celsius(), 100.0, abs <= 1e-12);
+        assert_float_eq!(MolarVolume::liter_per_mol(22.4).to_cubic_meter_per_mol(), 0.0224, ulps <= 1);
+    }
+
+    #[test]
+    fn typed_state_matches_si() {
+        let air = compounds::dry_air();
+        let p = Pressure::bar(200.0);
+        let t = Temperature::celsius(15.0);
+
+        assert_eq!(air.z_at::<PengRobinson>(p, t), air.z::<PengRobinson>(200e5, 288.15));
+        assert_eq!(
+            air.specific_mass_at::<PengRobinson>(p, t),
+            air.specific_mass::<PengRobinson>(200e5, 288.15)
+        );
+
+        let vm = air.molar_volume_at::<PengRobinson>(p, t);
+        assert_eq!(
+            vm.to_cubic_meter_per_mol(),
+            air.molar_volume::<PengRobinson>(200e5, 288.15)
+        );
+        assert_float_eq!(
+            air.pressure_at::<PengRobinson>(vm, t).to_pascal(),
+            200e5,
+            r2nd <= 1e-9
+        );
+    }
+}
@@ -0,0 +1,422 @@
+use crate::{Mixture, Phase, R, SolverError, SolverOptions, State, eos, eos::EquationOfState, eos::MixingRules, solver};
+#[cfg(all(not(feature = "std"), not(test)))]
+use crate::math::Float;
+#[cfg(not(feature = "std"))]
+use alloc::{vec, vec::Vec};
+
+impl Mixture {
+    /// Compute the dew point temperature at the pressure `p`, in K.
+    ///
+    /// This is the temperature at which the first drop of liquid appears when cooling the mixture,
+    /// that is where the mixture, as vapor, is in equilibrium with an incipient liquid phase.
+    /// Returns `None` if no dew point is found, e.g. above the cricondenbar or if the equation
+    /// of state does not predict a liquid phase.
+    pub fn dew_point_temperature<E: EquationOfState>(&self, p: f64) -> Option<f64>
+    where
+        E::Params: Clone,
+    {
+        self.dew_point_temperature_with_options::<E>(p, &SolverOptions::default())
+            .ok()
+    }
+
+    /// Compute the dew point temperature like [`Mixture::dew_point_temperature`],
+    /// with the convergence control of `options`
+    pub fn dew_point_temperature_with_options<E: EquationOfState>(
+        &self,
+        p: f64,
+        options: &SolverOptions,
+    ) -> Result<f64, SolverError>
+    where
+        E::Params: Clone,
+    {
+        self.incipient_phase_temperature::<E>(p, Phase::Liquid, options)
+    }
+
+    /// Compute the bubble point temperature at the pressure `p`, in K.
+    ///
+    /// This is the temperature at which the first bubble of vapor appears when heating the mixture,
+    /// that is where the mixture, as liquid, is in equilibrium with an incipient vapor phase.
+    /// Returns `None` if no bubble point is found.
+    pub fn bubble_point_temperature<E: EquationOfState>(&self, p: f64) -> Option<f64>
+    where
+        E::Params: Clone,
+    {
+        self.bubble_point_temperature_with_options::<E>(p, &SolverOptions::default())
+            .ok()
+    }
+
+    /// Compute the bubble point temperature like [`Mixture::bubble_point_temperature`],
+    /// with the convergence control of `options`
+    pub fn bubble_point_temperature_with_options<E: EquationOfState>(
+        &self,
+        p: f64,
+        options: &SolverOptions,
+    ) -> Result<f64, SolverError>
+    where
+        E::Params: Clone,
+    {
+        self.incipient_phase_temperature::<E>(p, Phase::Vapor, options)
+    }
+
+    /// Compute the fugacity coefficient φi of each component of the mixture as vapor,
+    /// at the pressure `p` and temperature `t`, in the order of [`Mixture::components`].
+    ///
+    /// ln φi is the derivative of the residual Helmholtz energy n·(A - A_ig)/RT with respect
+    /// to the amount of the component at constant temperature and volume, minus ln Z.
+    /// The derivative is computed numerically, so that it applies to the mixing rules
+    /// of any equation of state. It agrees with the classical closed form of cubic equations
+    /// of state, involving ∂(nB)/∂ni and Σj xj·aij, to about 1e-7.
+    ///
+    /// # Panics
+    /// Panics if no positive real root can be found, like [`State::z`].
+    pub fn component_fugacity_coeffs<E: EquationOfState>(&self, p: f64, t: f64) -> Vec<f64>
+    where
+        E::Params: Clone,
+    {
+        let comps: Vec<(f64, E::Params)> = self
+            .comps
+            .iter()
+            .map(|(f, m)| (*f, m.eos_params::<E>(t)))
+            .collect();
+        let fractions: Vec<f64> = self.comps.iter().map(|(f, _)| *f).collect();
+        ln_fugacity_coefficients::<E>(&comps, &fractions, p, t, Phase::Vapor)
+            .expect("Should have a found a positive real root")
+            .into_iter()
+            .map(f64::exp)
+            .collect()
+    }
+
+    /// The temperature at which the `incipient` phase appears from the mixture at `p`
+    fn incipient_phase_temperature<E: EquationOfState>(
+        &self,
+        p: f64,
+        incipient: Phase,
+        options: &SolverOptions,
+    ) -> Result<f64, SolverError>
+    where
+        E::Params: Clone,
+    {
+        const RATIO: f64 = 1.02;
+        const STEPS: usize = 100;
+
+        // decreasing with the temperature, and zero at the dew or bubble point
+        let g = |t: f64| {
+            let ln_sum = self.incipient_phase_sum::<E>(p, t, incipient)?;
+            Some(match incipient {
+                Phase::Liquid => ln_sum,
+                Phase::Vapor => -ln_sum,
+            })
+        };
+
+        let mut t0 = self.wilson_temperature(p, incipient, options)?;
+        let mut g0 = g(t0).ok_or(SolverError::NoSolution)?;
+        let ratio = if g0 > 0.0 { RATIO } else { 1.0 / RATIO };
+        for _ in 0..STEPS {
+            let t1 = t0 * ratio;
+            let g1 = g(t1).ok_or(SolverError::NoSolution)?;
+            if g1.signum() != g0.signum() {
+                let f = |t: f64| g(t).unwrap_or(f64::NAN);
+                return solver::find_root_brent(t0, t1, f, t1, options);
+            }
+            (t0, g0) = (t1, g1);
+        }
+        Err(SolverError::NoSolution)
+    }
+
+    /// The temperature where the sum of the incipient phase fractions estimated
+    /// with the K-values of Wilson is 1
+    fn wilson_temperature(&self, p: f64, incipient: Phase, options: &SolverOptions) -> Result<f64, SolverError> {
+        let g = |t: f64| {
+            let sum: f64 = self
+                .comps
+                .iter()
+                .map(|(f, m)| {
+                    let k = wilson_k(m, p, t);
+                    match incipient {
+                        Phase::Liquid => f / k,
+                        Phase::Vapor => f * k,
+                    }
+                })
+                .sum();
+            sum.ln()
+        };
+        let tc = |(_, m): &(f64, crate::Molecule)| m.critical_state.t;
+        let t_lo = 0.2 * self.comps.iter().map(tc).fold(f64::INFINITY, f64::min);
+        let t_hi = 3.0 * self.comps.iter().map(tc).fold(0.0, f64::max);
+        solver::find_root_brent(t_lo, t_hi, g, t_hi, options)
+    }
+
+    /// The logarithm of the sum of the incipient phase fractions at `p` and `t`,
+    /// once the K-values are converged by successive substitution.
+    /// Returns `None` for the trivial solution, where both phases are identical.
+    fn incipient_phase_sum<E: EquationOfState>(&self, p: f64, t: f64, incipient: Phase) -> Option<f64>
+    where
+        E::Params: Clone,
+    {
+        const MAX_ITER: usize = 200;
+
+        let comps: Vec<(f64, E::Params)> = self
+            .comps
+            .iter()
+            .map(|(f, m)| (*f, m.eos_params::<E>(t)))
+            .collect();
+        let feed: Vec<f64> = self.comps.iter().map(|(f, _)| *f).collect();
+        let feed_phase = match incipient {
+            Phase::Liquid => Phase::Vapor,
+            Phase::Vapor => Phase::Liquid,
+        };
+        let ln_phi_feed = ln_fugacity_coefficients::<E>(&comps, &feed, p, t, feed_phase)?;
+
+        // K = y/x, starting from the estimate of Wilson
+        let mut ln_k: Vec<f64> = self.comps.iter().map(|(_, m)| wilson_k(m, p, t).ln()).collect();
+        let mut fractions = vec![0.0; feed.len()];
+        for _ in 0..MAX_ITER {
+            for ((w, f), ln_k) in fractions.iter_mut().zip(&feed).zip(&ln_k) {
+                *w = match incipient {
+                    Phase::Liquid => f / ln_k.exp(),
+                    Phase::Vapor => f * ln_k.exp(),
+                };
+            }
+            let sum: f64 = fractions.iter().sum();
+            fractions.iter_mut().for_each(|w| *w /= sum);
+
+            let ln_phi = ln_fugacity_coefficients::<E>(&comps, &fractions, p, t, incipient)?;
+            let mut delta: f64 = 0.0;
+            for ((ln_k, phi_feed), phi) in ln_k.iter_mut().zip(&ln_phi_feed).zip(&ln_phi) {
+                let new = match incipient {
+                    Phase::Liquid => phi - phi_feed,
+                    Phase::Vapor => phi_feed - phi,
+                };
+                delta = delta.max((new - *ln_k).abs());
+                *ln_k = new;
+            }
+            if delta < 1e-10 {
+                break;
+            }
+        }
+        if ln_k.iter().all(|ln_k| ln_k.abs() < 1e-4) {
+            return None;
+        }
+
+        let sum: f64 = feed
+            .iter()
+            .zip(&ln_k)
+            .map(|(f, ln_k)| match incipient {
+                Phase::Liquid => f / ln_k.exp(),
+                Phase::Vapor => f * ln_k.exp(),
+            })
+            .sum();
+        Some(sum.ln())
+    }
+}
+
+/// The K-value y/x of the molecule estimated with the correlation of Wilson (1968)
+fn wilson_k(m: &crate::Molecule, p: f64, t: f64) -> f64 {
+    let (cs, w) = m.effective_critical_state(t);
+    cs.p / p * (5.373 * (1.0 + w) * (1.0 - cs.t / t)).exp()
+}
+
+/// The logarithms of the fugacity coefficients of the components of a phase
+/// with the molar `fractions`, at `p` and `t`.
+///
+/// They are the derivatives of the residual Helmholtz energy with respect to
+/// the amount of each component, at constant temperature and volume,
+/// computed with central finite differences.
+/// The step is relative to the fraction of each component, with a floor for trace components,
+/// whose amounts are only varied upward so that they never become negative.
+/// Returns `None` if the equation of state has no root.
+fn ln_fugacity_coefficients<E: EquationOfState>(
+    comps: &[(f64, E::Params)],
+    fractions: &[f64],
+    p: f64,
+    t: f64,
+    phase: Phase,
+) -> Option<Vec<f64>>
+where
+    E::Params: Clone,
+{
+    const H: f64 = 1e-5;
+
+    let mix = |amounts: &[f64]| {
+        let n: f64 = amounts.iter().sum();
+        let params: Vec<(f64, E::Params)> = comps
+            .iter()
+            .zip(amounts)
+            .map(|((_, params), a)| (a / n, params.clone()))
+            .collect();
+        (n, E::Params::mix(&params))
+    };
+
+    let (_, params) = mix(fractions);
+    let roots = eos::z_roots::<E>(&params, p, t);
+    let z = match phase {
+        Phase::Liquid => *roots.first()?,
+        Phase::Vapor => *roots.last()?,
+    };
+    // the volume of one mol of the phase
+    let v = z * R * t / p;
+
+    // n·(A - A_ig)/nRT for the amounts of the components, at the volume v
+    let residual = |amounts: &[f64]| {
+        let (n, params) = mix(amounts);
+        n * E::residual_helmholtz(&params, v / n, t)
+    };
+
+    let mut amounts = fractions.to_vec();
+    let ln_phi = fractions
+        .iter()
+        .enumerate()
+        .map(|(i, &x)| {
+            let h = H * x.max(H);
+            let (x_hi, x_lo) = (x + h, (x - h).max(0.0));
+            amounts[i] = x_hi;
+            let hi = residual(&amounts);
+            amounts[i] = x_lo;
+            let lo = residual(&amounts);
+            amounts[i] = x;
+            (hi - lo) / (x_hi - x_lo) - z.ln()
+        })
+        .collect();
+    Some(ln_phi)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        Comp, Mixture, SolverError, SolverOptions, State, compounds,
+        eos::{AbParams, IdealGas, MixingRules, PengRobinson},
+    };
+    use float_eq::assert_float_eq;
+
+    fn propane_butane() -> Mixture {
+        Mixture::new([
+            Comp::Factor(0.5, compounds::C3H8.into()),
+            Comp::Remainder(compounds::C4H10.into()),
+        ])
+        .unwrap()
+    }
+
+    #[test]
+    fn propane_butane_low_pressure() {
+        // at low pressure, the mixture follows the law of Raoult with the saturation pressures
+        let mix = propane_butane();
+        let p = 101325.0;
+        let psat = |t: f64| {
+            let p3 = compounds::C3H8.saturation_pressure::<PengRobinson>(t).unwrap();
+            let p4 = compounds::C4H10.saturation_pressure::<PengRobinson>(t).unwrap();
+            (p3, p4)
+        };
+
+        let t_dew = mix.dew_point_temperature::<PengRobinson>(p).unwrap();
+        let (p3, p4) = psat(t_dew);
+        assert_float_eq!(0.5 * p / p3 + 0.5 * p / p4, 1.0, abs <= 0.02);
+
+        let t_bubble = mix.bubble_point_temperature::<PengRobinson>(p).unwrap();
+        let (p3, p4) = psat(t_bubble);
+        assert_float_eq!(0.5 * p3 / p + 0.5 * p4 / p, 1.0, abs <= 0.02);
+
+        // between the boiling points of propane (231 K) and n-butane (273 K)
+        assert!(231.0 < t_bubble && t_bubble < t_dew && t_dew < 273.0);
+    }
+
+    #[test]
+    fn propane_butane_published_dew_point() {
+        // Propane and n-butane form a nearly ideal solution at atmospheric pressure.
+        // The reference dew (259.1 K) and bubble (242.5 K) points of the equimolar mixture
+        // follow from the law of Raoult with the experimental vapor pressures of the NIST Webbook
+        // (Antoine equations of Kemp and Egan, 1938, and of Das et al., 1973).
+        let mix = propane_butane();
+        let p = 101325.0;
+        assert_float_eq!(mix.dew_point_temperature::<PengRobinson>(p).unwrap(), 259.1, abs <= 1.0);
+        assert_float_eq!(mix.bubble_point_temperature::<PengRobinson>(p).unwrap(), 242.5, abs <= 2.5);
+
+        let options = SolverOptions {
+            max_iter: 1,
+            ..SolverOptions::default()
+        };
+        assert_eq!(
+            mix.dew_point_temperature_with_options::<PengRobinson>(p, &options),
+            Err(SolverError::DidNotConverge { iterations: 1 })
+        );
+        assert_eq!(
+            mix.bubble_point_temperature_with_options::<PengRobinson>(p, &SolverOptions::default()),
+            Ok(mix.bubble_point_temperature::<PengRobinson>(p).unwrap())
+        );
+    }
+
+    #[test]
+    fn near_pure_component() {
+        // dew and bubble points merge at the saturation temperature of the pure component
+        let mix = Mixture::new([
+            Comp::Factor(0.999, compounds::C3H8.into()),
+            Comp::Remainder(compounds::C4H10.into()),
+        ])
+        .unwrap();
+        let t = 250.0;
+        let p = compounds::C3H8.saturation_pressure::<PengRobinson>(t).unwrap();
+        let t_dew = mix.dew_point_temperature::<PengRobinson>(p).unwrap();
+        let t_bubble = mix.bubble_point_temperature::<PengRobinson>(p).unwrap();
+        assert_float_eq!(t_dew, t, abs <= 0.2);
+        assert_float_eq!(t_bubble, t, abs <= 0.2);
+        assert!(t_bubble < t_dew);
+    }
+
+    #[test]
+    fn fugacity_coeffs_of_pure_pseudo_mixture() {
+        let (p, t) = (50e5, 320.0);
+        let co2 = Mixture::new([Comp::Remainder(compounds::CO2.into())]).unwrap();
+        let phi = co2.component_fugacity_coeffs::<PengRobinson>(p, t);
+        assert_eq!(phi.len(), 1);
+        assert_float_eq!(phi[0], compounds::CO2.fugacity_coefficient::<PengRobinson>(p, t), rmax <= 1e-7);
+    }
+
+    #[test]
+    fn fugacity_coeffs_sum_to_mixture() {
+        // Σ xi·ln φi = ln φ of the mixture
+        let (p, t) = (20e5, 350.0);
+        let mix = propane_butane();
+        let phi = mix.component_fugacity_coeffs::<PengRobinson>(p, t);
+        let sum: f64 = mix.components().zip(&phi).map(|((x, _), phi)| x * phi.ln()).sum();
+        assert_float_eq!(sum, mix.fugacity_coefficient::<PengRobinson>(p, t).ln(), abs <= 1e-7);
+        // the heavier butane is less volatile
+        let phi_of = |m| mix.components().zip(&phi).find(|((_, cm), _)| **cm == m).unwrap().1;
+        assert!(phi_of(compounds::C4H10) < phi_of(compounds::C3H8));
+    }
+
+    #[test]
+    fn fugacity_coeffs_match_peng_robinson_closed_form() {
+        use crate::R;
+
+        let (p, t) = (100e5, 300.0);
+        // CO2 is a trace component in the last mixture
+        for x in [0.3, 1e-9] {
+            let mix = Mixture::new([
+                Comp::Factor(x, compounds::CO2.into()),
+                Comp::Remainder(compounds::N2.into()),
+            ])
+            .unwrap();
+            let phi = mix.component_fugacity_coeffs::<PengRobinson>(p, t);
+
+            let comps: alloc::vec::Vec<(f64, AbParams)> = mix
+                .components()
+                .map(|(f, m)| (f, m.eos_params::<PengRobinson>(t)))
+                .collect();
+            let AbParams { a, b } = AbParams::mix(&comps);
+            let z = mix.z::<PengRobinson>(p, t);
+            let (aa, bb) = (a * p / (R * t).powi(2), b * p / (R * t));
+            let sqrt2 = 2f64.sqrt();
+            let ln = ((z + (1.0 + sqrt2) * bb) / (z + (1.0 - sqrt2) * bb)).ln();
+            for ((_, pi), phi) in comps.iter().zip(&phi) {
+                let sum_a: f64 = comps.iter().map(|(xj, pj)| xj * (pi.a * pj.a).sqrt()).sum();
+                let ln_phi = pi.b / b * (z - 1.0) - (z - bb).ln()
+                    - aa / (2.0 * sqrt2 * bb) * (2.0 * sum_a / a - pi.b / b) * ln;
+                assert_float_eq!(phi.ln(), ln_phi, abs <= 1e-7);
+            }
+        }
+    }
+
+    #[test]
+    fn no_liquid_phase() {
+        assert_eq!(propane_butane().dew_point_temperature::<IdealGas>(101325.0), None);
+    }
+}
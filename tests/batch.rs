@@ -0,0 +1,30 @@
+//! Run the `batch` subcommand of the CLI on a two-case file.
+
+use std::{env, fs, process::Command};
+
+#[test]
+fn batch_file() {
+    let path = env::temp_dir().join(format!("realgas-batch-{}.csv", std::process::id()));
+    fs::write(
+        &path,
+        "gas,eos,pressure,temperature\n80%N2+O2,pr,200,15\nUnobtainium,pr,200,15\n",
+    )
+    .unwrap();
+
+    let output = Command::new(env!("CARGO_BIN_EXE_realgas"))
+        .arg("batch")
+        .arg(&path)
+        .output()
+        .unwrap();
+    fs::remove_file(&path).unwrap();
+
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let lines: Vec<&str> = stdout.lines().collect();
+    assert_eq!(lines.len(), 3);
+    assert_eq!(lines[0], "gas,eos,pressure,temperature,z");
+
+    let z: f64 = lines[1].strip_prefix("80%N2+O2,pr,200,15,").unwrap().parse().unwrap();
+    assert!((z - 1.0).abs() < 0.05, "z = {z}");
+    assert!(lines[2].starts_with("Unobtainium,pr,200,15,\"error: "));
+}
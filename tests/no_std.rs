@@ -0,0 +1,27 @@
+//! Build the library without the `std` feature and check that it computes the same Z as with `std`.
+//!
+//! Run with `cargo test --no-default-features --test no_std`.
+
+use float_eq::assert_float_eq;
+use realgas::{StateEos, compounds, eos::Eos};
+
+#[test]
+fn nitrogen_z_matches_std() {
+    // reference values computed with the std build
+    let expected = [
+        (Eos::IdealGas, 1.000000000000000),
+        (Eos::VanDerWaals, 1.007706044653317),
+        (Eos::Clausius, 0.984811337524382),
+        (Eos::Berthelot, 1.195459304905221),
+        (Eos::RedlichKwong, 1.031185170649679),
+        (Eos::SoaveRedlichKwong, 1.076901397837552),
+        (Eos::PengRobinson, 1.028809282803002),
+        (Eos::PengRobinson1976, 1.028610337354309),
+        (Eos::PatelTejaValderrama, 1.064138552877135),
+        (Eos::LeeKesler, 1.060180986360884),
+    ];
+    let n2 = compounds::lookup("N2").unwrap();
+    for (eos, z) in expected {
+        assert_float_eq!(n2.z_eos(eos, 200e5, 300.0), z, r2nd <= 1e-12);
+    }
+}